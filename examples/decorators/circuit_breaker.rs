@@ -2,8 +2,8 @@
 //!
 //! Prevents cascading failures by temporarily blocking calls to a failing service.
 
-use std::collections::HashMap;
-use std::sync::{LazyLock, Mutex};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, LazyLock, Mutex};
 use std::time::{Duration, Instant};
 use tracing::{error, info, warn};
 
@@ -18,30 +18,150 @@ pub enum CircuitState {
     HalfOpen,
 }
 
+/// Condition under which a closed circuit opens.
+#[derive(Debug)]
+enum TripPolicy {
+    /// Open after this many consecutive failures
+    Count(u32),
+    /// Open when the failure rate within the window exceeds `failure_rate`,
+    /// once at least `min_samples` calls have been observed
+    Rate {
+        failure_rate: f64,
+        min_samples: u32,
+        window: Duration,
+    },
+    /// Open when the failure rate over the last `window_size` calls exceeds
+    /// `failure_rate`, once the window has filled
+    RecentCalls {
+        failure_rate: f64,
+        window_size: usize,
+    },
+}
+
 #[derive(Debug)]
 struct CircuitBreaker {
     state: CircuitState,
     failure_count: u32,
     success_count: u32,
     last_failure_time: Option<Instant>,
-    failure_threshold: u32,
+    policy: TripPolicy,
+    /// Recent call outcomes (`true` for success), for rate-based breakers
+    outcomes: VecDeque<(Instant, bool)>,
     success_threshold: u32,
     timeout: Duration,
 }
 
 impl CircuitBreaker {
     fn new(failure_threshold: u32, success_threshold: u32, timeout: Duration) -> Self {
+        Self::with_policy(
+            TripPolicy::Count(failure_threshold),
+            success_threshold,
+            timeout,
+        )
+    }
+
+    fn new_windowed(
+        failure_rate: f64,
+        min_samples: u32,
+        window: Duration,
+        timeout: Duration,
+    ) -> Self {
+        Self::with_policy(
+            TripPolicy::Rate {
+                failure_rate,
+                min_samples,
+                window,
+            },
+            1,
+            timeout,
+        )
+    }
+
+    fn new_rate_based(
+        window_size: usize,
+        failure_rate_threshold: f64,
+        success_threshold: u32,
+        timeout: Duration,
+    ) -> Self {
+        Self::with_policy(
+            TripPolicy::RecentCalls {
+                failure_rate: failure_rate_threshold,
+                window_size: window_size.max(1),
+            },
+            success_threshold,
+            timeout,
+        )
+    }
+
+    fn with_policy(policy: TripPolicy, success_threshold: u32, timeout: Duration) -> Self {
         Self {
             state: CircuitState::Closed,
             failure_count: 0,
             success_count: 0,
             last_failure_time: None,
-            failure_threshold,
+            policy,
+            outcomes: VecDeque::new(),
             success_threshold,
             timeout,
         }
     }
 
+    /// Closes the circuit and forgets all recorded failures and outcomes.
+    fn reset(&mut self) {
+        self.state = CircuitState::Closed;
+        self.failure_count = 0;
+        self.success_count = 0;
+        self.outcomes.clear();
+    }
+
+    /// Records a call outcome in the sliding window, dropping expired ones.
+    fn record_outcome(&mut self, success: bool) {
+        let now = Instant::now();
+        match self.policy {
+            TripPolicy::Count(_) => return,
+            TripPolicy::Rate { window, .. } => {
+                while self
+                    .outcomes
+                    .front()
+                    .is_some_and(|(at, _)| now.duration_since(*at) > window)
+                {
+                    self.outcomes.pop_front();
+                }
+            }
+            TripPolicy::RecentCalls { window_size, .. } => {
+                if self.outcomes.len() == window_size {
+                    self.outcomes.pop_front();
+                }
+            }
+        }
+        self.outcomes.push_back((now, success));
+    }
+
+    /// Whether more than `failure_rate` of the recorded outcomes are failures.
+    fn failure_rate_exceeds(&self, failure_rate: f64) -> bool {
+        let samples = self.outcomes.len();
+        let failures = self.outcomes.iter().filter(|(_, ok)| !ok).count();
+        failures as f64 / samples as f64 > failure_rate
+    }
+
+    fn should_trip(&self) -> bool {
+        match self.policy {
+            TripPolicy::Count(failure_threshold) => self.failure_count >= failure_threshold,
+            TripPolicy::Rate {
+                failure_rate,
+                min_samples,
+                ..
+            } => {
+                self.outcomes.len() >= min_samples as usize
+                    && self.failure_rate_exceeds(failure_rate)
+            }
+            TripPolicy::RecentCalls {
+                failure_rate,
+                window_size,
+            } => self.outcomes.len() >= window_size && self.failure_rate_exceeds(failure_rate),
+        }
+    }
+
     fn can_execute(&mut self) -> bool {
         match self.state {
             CircuitState::Closed => true,
@@ -75,10 +195,12 @@ impl CircuitBreaker {
                     self.state = CircuitState::Closed;
                     self.failure_count = 0;
                     self.success_count = 0;
+                    self.outcomes.clear();
                 }
             }
             CircuitState::Closed => {
                 self.failure_count = 0; // Reset on success
+                self.record_outcome(true);
             }
             _ => {}
         }
@@ -87,16 +209,18 @@ impl CircuitBreaker {
     fn record_failure(&mut self) {
         self.failure_count += 1;
         self.last_failure_time = Some(Instant::now());
+        if self.state == CircuitState::Closed {
+            self.record_outcome(false);
+        }
 
         match self.state {
-            CircuitState::Closed => {
-                if self.failure_count >= self.failure_threshold {
-                    error!(
-                        "🔴 Circuit breaker opened after {} failures",
-                        self.failure_count
-                    );
-                    self.state = CircuitState::Open;
-                }
+            CircuitState::Closed if self.should_trip() => {
+                error!(
+                    "🔴 Circuit breaker opened after {} failures",
+                    self.failure_count
+                );
+                self.state = CircuitState::Open;
+                self.outcomes.clear();
             }
             CircuitState::HalfOpen => {
                 warn!("🔴 Circuit breaker re-opened after failure in half-open state");
@@ -113,6 +237,54 @@ type CircuitBreakerMap = HashMap<String, CircuitBreaker>;
 static CIRCUIT_BREAKERS: LazyLock<Mutex<CircuitBreakerMap>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
 
+type TransitionCallback = Arc<dyn Fn(CircuitState, CircuitState) + Send + Sync>;
+
+static TRANSITION_CALLBACKS: LazyLock<Mutex<HashMap<String, Vec<TransitionCallback>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Registers `callback` to run whenever the named circuit changes state.
+///
+/// The callback receives the old and new states and runs after the breaker's
+/// lock is released, so it may query or reset circuits itself. Several
+/// callbacks can be registered for the same circuit.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// on_circuit_transition("api", |from, to| {
+///     if to == CircuitState::Open {
+///         alert(format!("api circuit opened (was {from:?})"));
+///     }
+/// });
+/// ```
+pub fn on_circuit_transition(
+    name: &str,
+    callback: impl Fn(CircuitState, CircuitState) + Send + Sync + 'static,
+) {
+    TRANSITION_CALLBACKS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .entry(name.to_string())
+        .or_default()
+        .push(Arc::new(callback));
+}
+
+/// Runs the named circuit's transition callbacks if its state changed.
+fn notify_transition(name: &str, from: CircuitState, to: CircuitState) {
+    if from == to {
+        return;
+    }
+    let callbacks = TRANSITION_CALLBACKS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(name)
+        .cloned()
+        .unwrap_or_default();
+    for callback in callbacks {
+        callback(from, to);
+    }
+}
+
 /// Circuit breaker decorator for fault tolerance.
 ///
 /// # Arguments
@@ -143,31 +315,266 @@ pub fn circuit_breaker<F, R, E>(
 where
     F: FnOnce() -> Result<R, E>,
     E: std::fmt::Debug + From<String>,
+{
+    execute_with_breaker(
+        name,
+        || {
+            CircuitBreaker::new(
+                failure_threshold,
+                success_threshold,
+                Duration::from_secs(timeout_secs),
+            )
+        },
+        |_| true,
+        f,
+    )
+}
+
+/// Circuit breaker that only counts errors matching `is_failure` as failures.
+///
+/// Errors the predicate rejects (e.g. "not found") are still returned as `Err`
+/// but count as successes for breaker purposes, so they never open the circuit.
+///
+/// # Arguments
+/// * `name` - Unique identifier for this circuit breaker
+/// * `failure_threshold` - Number of failures before opening the circuit
+/// * `success_threshold` - Number of successes in half-open state before closing
+/// * `timeout_secs` - Seconds to wait before transitioning from open to half-open
+/// * `is_failure` - Returns `true` for errors that should count as failures
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(circuit_breaker_if("api", 5, 2, 30, |e: &ApiError| !e.is_not_found()))]
+/// fn fetch_user(id: u64) -> Result<User, ApiError> {
+///     // Missing users don't count against the service's health
+/// }
+/// ```
+pub fn circuit_breaker_if<F, R, E, P>(
+    name: &str,
+    failure_threshold: u32,
+    success_threshold: u32,
+    timeout_secs: u64,
+    is_failure: P,
+    f: F,
+) -> Result<R, E>
+where
+    F: FnOnce() -> Result<R, E>,
+    E: std::fmt::Debug + From<String>,
+    P: FnOnce(&E) -> bool,
+{
+    execute_with_breaker(
+        name,
+        || {
+            CircuitBreaker::new(
+                failure_threshold,
+                success_threshold,
+                Duration::from_secs(timeout_secs),
+            )
+        },
+        is_failure,
+        f,
+    )
+}
+
+/// Circuit breaker that opens on the failure rate within a sliding time window.
+///
+/// Unlike [`circuit_breaker`], which counts consecutive failures, this accounts
+/// for call volume: a few failures among many successes keep the circuit closed.
+/// A single success in the half-open state closes the circuit again.
+///
+/// # Arguments
+/// * `name` - Unique identifier for this circuit breaker
+/// * `failure_rate` - Failure rate (between 0.0 and 1.0) that must be exceeded to open
+/// * `min_samples` - Minimum calls within the window before the rate is considered
+/// * `window` - Length of the sliding window
+/// * `timeout` - Time to wait before transitioning from open to half-open
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(circuit_breaker_rate("api", 0.5, 20, Duration::from_secs(60), Duration::from_secs(30)))]
+/// fn call_external_api() -> Result<Response, Error> {
+///     // Opens when more than half of at least 20 calls in the last minute fail
+/// }
+/// ```
+pub fn circuit_breaker_rate<F, R, E>(
+    name: &str,
+    failure_rate: f64,
+    min_samples: u32,
+    window: Duration,
+    timeout: Duration,
+    f: F,
+) -> Result<R, E>
+where
+    F: FnOnce() -> Result<R, E>,
+    E: std::fmt::Debug + From<String>,
+{
+    execute_with_breaker(
+        name,
+        || CircuitBreaker::new_windowed(failure_rate, min_samples, window, timeout),
+        |_| true,
+        f,
+    )
+}
+
+/// Circuit breaker that opens on the failure rate over the most recent calls.
+///
+/// Like [`circuit_breaker_rate`], but the window holds the last `window_size`
+/// outcomes rather than those within a time span, so the rate is only
+/// considered once that many calls have been observed.
+///
+/// # Arguments
+/// * `name` - Unique identifier for this circuit breaker
+/// * `window_size` - Number of recent calls the failure rate is computed over
+/// * `failure_rate_threshold` - Failure rate (between 0.0 and 1.0) that must be exceeded to open
+/// * `success_threshold` - Number of successes in half-open state before closing
+/// * `timeout` - Time to wait before transitioning from open to half-open
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(circuit_breaker_rate_based("api", 20, 0.5, 2, Duration::from_secs(30)))]
+/// fn call_external_api() -> Result<Response, Error> {
+///     // Opens when more than half of the last 20 calls failed
+/// }
+/// ```
+pub fn circuit_breaker_rate_based<F, R, E>(
+    name: &str,
+    window_size: usize,
+    failure_rate_threshold: f64,
+    success_threshold: u32,
+    timeout: Duration,
+    f: F,
+) -> Result<R, E>
+where
+    F: FnOnce() -> Result<R, E>,
+    E: std::fmt::Debug + From<String>,
+{
+    execute_with_breaker(
+        name,
+        || {
+            CircuitBreaker::new_rate_based(
+                window_size,
+                failure_rate_threshold,
+                success_threshold,
+                timeout,
+            )
+        },
+        |_| true,
+        f,
+    )
+}
+
+/// Circuit breaker that serves a fallback instead of an error while open.
+///
+/// Behaves like [`circuit_breaker`], except that a rejected call runs
+/// `fallback` rather than returning an error, e.g. to serve cached or stale
+/// data. The fallback's outcome is not recorded against the breaker.
+///
+/// # Arguments
+/// * `name` - Unique identifier for this circuit breaker
+/// * `failure_threshold` - Number of failures before opening the circuit
+/// * `success_threshold` - Number of successes in half-open state before closing
+/// * `timeout_secs` - Seconds to wait before transitioning from open to half-open
+/// * `fallback` - The function to execute while the circuit is open
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(circuit_breaker_fallback("prices", 5, 2, 30, || Ok(stale_prices())))]
+/// fn fetch_prices() -> Result<Prices, Error> {
+///     // Stale prices are served while the pricing service is failing
+/// }
+/// ```
+pub fn circuit_breaker_fallback<F, G, R, E>(
+    name: &str,
+    failure_threshold: u32,
+    success_threshold: u32,
+    timeout_secs: u64,
+    fallback: G,
+    f: F,
+) -> Result<R, E>
+where
+    F: FnOnce() -> Result<R, E>,
+    G: FnOnce() -> Result<R, E>,
+    E: std::fmt::Debug,
+{
+    execute_or_fallback(
+        name,
+        || {
+            CircuitBreaker::new(
+                failure_threshold,
+                success_threshold,
+                Duration::from_secs(timeout_secs),
+            )
+        },
+        |_| true,
+        fallback,
+        f,
+    )
+}
+
+/// Runs `f` through the named breaker, creating it with `create` on first use.
+///
+/// Only errors for which `is_failure` returns `true` are recorded as failures.
+fn execute_with_breaker<F, R, E>(
+    name: &str,
+    create: impl FnOnce() -> CircuitBreaker,
+    is_failure: impl FnOnce(&E) -> bool,
+    f: F,
+) -> Result<R, E>
+where
+    F: FnOnce() -> Result<R, E>,
+    E: std::fmt::Debug + From<String>,
+{
+    execute_or_fallback(
+        name,
+        create,
+        is_failure,
+        || Err(E::from(format!("Circuit breaker '{}' is open", name))),
+        f,
+    )
+}
+
+/// Like [`execute_with_breaker`], running `on_open` instead of `f` when the
+/// breaker rejects the call.
+fn execute_or_fallback<F, R, E>(
+    name: &str,
+    create: impl FnOnce() -> CircuitBreaker,
+    is_failure: impl FnOnce(&E) -> bool,
+    on_open: impl FnOnce() -> Result<R, E>,
+    f: F,
+) -> Result<R, E>
+where
+    F: FnOnce() -> Result<R, E>,
+    E: std::fmt::Debug,
 {
     let mut breakers = CIRCUIT_BREAKERS
         .lock()
         .unwrap_or_else(|poisoned| poisoned.into_inner());
 
-    let breaker = breakers.entry(name.to_string()).or_insert_with(|| {
-        CircuitBreaker::new(
-            failure_threshold,
-            success_threshold,
-            Duration::from_secs(timeout_secs),
-        )
-    });
+    let breaker = breakers.entry(name.to_string()).or_insert_with(create);
+
+    let initial_state = breaker.state;
+    let allowed = breaker.can_execute();
+    let state_before = breaker.state;
+    drop(breakers); // Release lock during execution
+    notify_transition(name, initial_state, state_before);
 
-    if !breaker.can_execute() {
+    if !allowed {
         warn!(
             circuit = %name,
-            state = ?breaker.state,
+            state = ?state_before,
             "🚫 Circuit breaker is open, rejecting request"
         );
-        return Err(E::from(format!("Circuit breaker '{}' is open", name)));
+        return on_open();
     }
 
-    let state_before = breaker.state;
-    drop(breakers); // Release lock during execution
-
     let result = f();
 
     let mut breakers = CIRCUIT_BREAKERS
@@ -175,10 +582,20 @@ where
         .unwrap_or_else(|poisoned| poisoned.into_inner());
 
     if let Some(breaker) = breakers.get_mut(name) {
+        // Other calls may have moved the breaker while `f` ran
+        let from = breaker.state;
         match &result {
+            Err(e) if !is_failure(e) => {
+                breaker.record_success();
+                info!(
+                    circuit = %name,
+                    error = ?e,
+                    "➖ Error not classified as a failure"
+                );
+            }
             Ok(_) => {
                 breaker.record_success();
-                if state_before == CircuitState::HalfOpen {
+                if from == CircuitState::HalfOpen {
                     info!(circuit = %name, "✅ Success in half-open state");
                 }
             }
@@ -192,6 +609,9 @@ where
                 );
             }
         }
+        let to = breaker.state;
+        drop(breakers);
+        notify_transition(name, from, to);
     }
 
     result
@@ -207,12 +627,53 @@ pub fn get_circuit_state(name: &str) -> Option<CircuitState> {
 
 /// Resets a circuit breaker to closed state.
 pub fn reset_circuit(name: &str) {
-    if let Ok(mut breakers) = CIRCUIT_BREAKERS.lock() {
-        if let Some(breaker) = breakers.get_mut(name) {
-            breaker.state = CircuitState::Closed;
-            breaker.failure_count = 0;
-            breaker.success_count = 0;
-            info!(circuit = %name, "🔄 Circuit breaker reset");
-        }
+    let Ok(mut breakers) = CIRCUIT_BREAKERS.lock() else {
+        return;
+    };
+    let Some(breaker) = breakers.get_mut(name) else {
+        return;
+    };
+
+    let from = breaker.state;
+    breaker.reset();
+    drop(breakers);
+    info!(circuit = %name, "🔄 Circuit breaker reset");
+    notify_transition(name, from, CircuitState::Closed);
+}
+
+/// Resets every registered circuit breaker to closed state.
+pub fn reset_all_circuits() {
+    let Ok(mut breakers) = CIRCUIT_BREAKERS.lock() else {
+        return;
+    };
+
+    let transitions: Vec<_> = breakers
+        .iter_mut()
+        .map(|(name, breaker)| {
+            let from = breaker.state;
+            breaker.reset();
+            (name.clone(), from)
+        })
+        .collect();
+    drop(breakers);
+    info!(circuits = %transitions.len(), "🔄 All circuit breakers reset");
+
+    for (name, from) in transitions {
+        notify_transition(&name, from, CircuitState::Closed);
     }
 }
+
+/// Lists all registered circuit breakers with their current states, sorted by name.
+pub fn list_circuits() -> Vec<(String, CircuitState)> {
+    let mut circuits: Vec<_> = CIRCUIT_BREAKERS
+        .lock()
+        .map(|breakers| {
+            breakers
+                .iter()
+                .map(|(name, breaker)| (name.clone(), breaker.state))
+                .collect()
+        })
+        .unwrap_or_default();
+    circuits.sort_by(|a, b| a.0.cmp(&b.0));
+    circuits
+}
@@ -0,0 +1,233 @@
+//! Runtime composition of the `#[decorate]` configuration options.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use tracing::Span;
+
+type Hook = Box<dyn Fn() + Send + Sync>;
+type Inspect<R> = Box<dyn Fn(&R) + Send + Sync>;
+type Transform<T> = Box<dyn Fn(T) -> T + Send + Sync>;
+type CatchHandler<R> = Box<dyn Fn(Box<dyn Any + Send>) -> R + Send + Sync>;
+type Around<R> = Box<dyn for<'a> Fn(Box<dyn FnOnce() -> R + 'a>) -> R + Send + Sync>;
+type Condition = Box<dyn Fn() -> bool + Send + Sync>;
+
+/// A decorator stack assembled at runtime, mirroring the attribute's options.
+///
+/// `R` is the body's return type and `P` its parameters as a tuple. Stages run
+/// in the same order as in `#[decorate(...)]`: `transform_params`, `pre`, the
+/// body, `post`, `finally`, the option hooks, `transform_result`, `catch`,
+/// `instrument` and finally `around`, all gated by `when`. Options that only
+/// make sense at compile time, such as `keep_original` or `mockable`, have no
+/// runtime equivalent.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let config = DecorateConfig::<f64, (i32, i32)>::builder()
+///     .transform_params(|(x, y)| (x.max(0), y.max(1)))
+///     .pre(|| info!("dividing"))
+///     .transform_result(|r| (r * 100.0).round() / 100.0)
+///     .build();
+///
+/// let ratio = config.call((7, 3), |(x, y)| x as f64 / y as f64);
+/// ```
+pub struct DecorateConfig<R, P = ()> {
+    pre: Option<Hook>,
+    post: Option<Hook>,
+    finally: Option<Hook>,
+    transform_params: Option<Transform<P>>,
+    on_some: Option<Inspect<R>>,
+    on_none: Option<Inspect<R>>,
+    transform_result: Option<Transform<R>>,
+    catch: Option<CatchHandler<R>>,
+    instrument: Option<Span>,
+    around: Option<Around<R>>,
+    when: Option<Condition>,
+}
+
+impl<R, P> DecorateConfig<R, P> {
+    /// Starts an empty configuration, which runs the body unchanged.
+    pub fn builder() -> DecorateConfigBuilder<R, P> {
+        DecorateConfigBuilder {
+            config: Self {
+                pre: None,
+                post: None,
+                finally: None,
+                transform_params: None,
+                on_some: None,
+                on_none: None,
+                transform_result: None,
+                catch: None,
+                instrument: None,
+                around: None,
+                when: None,
+            },
+        }
+    }
+
+    /// Runs `body` with `params` under the configured stages.
+    pub fn call<B>(&self, params: P, body: B) -> R
+    where
+        B: FnOnce(P) -> R,
+    {
+        if self.when.as_ref().is_some_and(|when| !when()) {
+            return body(params);
+        }
+
+        let run = || {
+            let params = match &self.transform_params {
+                Some(transform) => transform(params),
+                None => params,
+            };
+            let result = {
+                let _finally = FinallyGuard(self.finally.as_deref());
+                if let Some(pre) = &self.pre {
+                    pre();
+                }
+                let result = body(params);
+                if let Some(post) = &self.post {
+                    post();
+                }
+                result
+            };
+            for hook in self.on_some.iter().chain(&self.on_none) {
+                hook(&result);
+            }
+            match &self.transform_result {
+                Some(transform) => transform(result),
+                None => result,
+            }
+        };
+
+        let run = || match &self.catch {
+            Some(handler) => panic::catch_unwind(AssertUnwindSafe(run)).unwrap_or_else(handler),
+            None => run(),
+        };
+
+        let run = || match &self.instrument {
+            Some(span) => span.in_scope(run),
+            None => run(),
+        };
+
+        match &self.around {
+            Some(around) => around(Box::new(run)),
+            None => run(),
+        }
+    }
+}
+
+impl<R> DecorateConfig<R> {
+    /// Runs a parameterless `body` under the configured stages.
+    pub fn wrap<B>(&self, body: B) -> R
+    where
+        B: FnOnce() -> R,
+    {
+        self.call((), |()| body())
+    }
+}
+
+/// Runs the `finally` hook when dropped, including while unwinding.
+struct FinallyGuard<'a>(Option<&'a (dyn Fn() + Send + Sync)>);
+
+impl Drop for FinallyGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(finally) = self.0 {
+            finally();
+        }
+    }
+}
+
+/// Builder for [`DecorateConfig`]; each method mirrors the attribute option of
+/// the same name and replaces any earlier value.
+pub struct DecorateConfigBuilder<R, P = ()> {
+    config: DecorateConfig<R, P>,
+}
+
+impl<R, P> DecorateConfigBuilder<R, P> {
+    /// Runs `hook` before the body.
+    pub fn pre(mut self, hook: impl Fn() + Send + Sync + 'static) -> Self {
+        self.config.pre = Some(Box::new(hook));
+        self
+    }
+
+    /// Runs `hook` after the body returns; a panic skips it.
+    pub fn post(mut self, hook: impl Fn() + Send + Sync + 'static) -> Self {
+        self.config.post = Some(Box::new(hook));
+        self
+    }
+
+    /// Runs `hook` after the body, on return and while unwinding.
+    pub fn finally(mut self, hook: impl Fn() + Send + Sync + 'static) -> Self {
+        self.config.finally = Some(Box::new(hook));
+        self
+    }
+
+    /// Maps the parameters before they reach the body.
+    pub fn transform_params(mut self, transform: impl Fn(P) -> P + Send + Sync + 'static) -> Self {
+        self.config.transform_params = Some(Box::new(transform));
+        self
+    }
+
+    /// Maps the body's result.
+    pub fn transform_result(mut self, transform: impl Fn(R) -> R + Send + Sync + 'static) -> Self {
+        self.config.transform_result = Some(Box::new(transform));
+        self
+    }
+
+    /// Recovers from a panic by converting its payload into a result.
+    pub fn catch(
+        mut self,
+        handler: impl Fn(Box<dyn Any + Send>) -> R + Send + Sync + 'static,
+    ) -> Self {
+        self.config.catch = Some(Box::new(handler));
+        self
+    }
+
+    /// Runs everything inside `span`.
+    pub fn instrument(mut self, span: Span) -> Self {
+        self.config.instrument = Some(span);
+        self
+    }
+
+    /// Wraps all other stages, receiving them as a closure.
+    pub fn around(
+        mut self,
+        around: impl for<'a> Fn(Box<dyn FnOnce() -> R + 'a>) -> R + Send + Sync + 'static,
+    ) -> Self {
+        self.config.around = Some(Box::new(around));
+        self
+    }
+
+    /// Skips every stage and runs the plain body while `condition` is false.
+    pub fn when(mut self, condition: impl Fn() -> bool + Send + Sync + 'static) -> Self {
+        self.config.when = Some(Box::new(condition));
+        self
+    }
+
+    /// Finishes the configuration.
+    pub fn build(self) -> DecorateConfig<R, P> {
+        self.config
+    }
+}
+
+impl<T, P> DecorateConfigBuilder<Option<T>, P> {
+    /// Runs `hook` when the body returns `Some`.
+    pub fn on_some(mut self, hook: impl Fn() + Send + Sync + 'static) -> Self {
+        self.config.on_some = Some(Box::new(move |result: &Option<T>| {
+            if result.is_some() {
+                hook();
+            }
+        }));
+        self
+    }
+
+    /// Runs `hook` when the body returns `None`.
+    pub fn on_none(mut self, hook: impl Fn() + Send + Sync + 'static) -> Self {
+        self.config.on_none = Some(Box::new(move |result: &Option<T>| {
+            if result.is_none() {
+                hook();
+            }
+        }));
+        self
+    }
+}
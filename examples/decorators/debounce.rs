@@ -9,6 +9,23 @@ type DebounceMap = HashMap<String, Instant>;
 
 static DEBOUNCE_STATE: LazyLock<Mutex<DebounceMap>> = LazyLock::new(|| Mutex::new(HashMap::new()));
 
+/// Last call, executed or not, for each leading-edge key.
+static LEADING_STATE: LazyLock<Mutex<DebounceMap>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Burst state of a [`debounce_max_wait`] key.
+struct MaxWaitState {
+    /// Last call, executed or not
+    last_call: Instant,
+    /// First call suppressed since the last execution
+    first_suppressed_at: Option<Instant>,
+}
+
+static MAX_WAIT_STATE: LazyLock<Mutex<HashMap<String, MaxWaitState>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Next instant each periodic key is due to run again.
+static INTERVAL_STATE: LazyLock<Mutex<DebounceMap>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
 /// Debounces function calls, preventing execution if called too frequently.
 ///
 /// Unlike rate limiting which delays execution, debouncing skips the call entirely
@@ -60,6 +77,137 @@ where
     Some(f())
 }
 
+/// Which call of a burst [`debounce_edge`] executes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebounceEdge {
+    /// Execute the first call of a burst; every call, executed or not, extends
+    /// the burst until a full window passes without calls
+    Leading,
+    /// Execute a call once the window has elapsed since the last executed one,
+    /// like [`debounce`]
+    Trailing,
+}
+
+/// Debounces function calls, choosing which edge of a burst executes.
+///
+/// With [`DebounceEdge::Leading`], the first call fires immediately and calls
+/// keep being skipped for as long as they arrive less than `window_ms` apart,
+/// e.g. to save on the first keystroke of a typing burst. With
+/// [`DebounceEdge::Trailing`], it behaves exactly like [`debounce`].
+///
+/// # Arguments
+/// * `key` - Unique identifier for this debounce group
+/// * `window_ms` - Quiet period in milliseconds that ends a burst
+/// * `edge` - Which call of a burst executes
+/// * `f` - The function to execute
+///
+/// # Returns
+/// `Some(R)` if executed, `None` if debounced
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(debounce_edge("draft", 2000, DebounceEdge::Leading))]
+/// fn save_draft() -> Option<()> {
+///     // Saves on the first keystroke, then not again until typing pauses for 2s
+/// }
+/// ```
+pub fn debounce_edge<F, R>(key: &str, window_ms: u64, edge: DebounceEdge, f: F) -> Option<R>
+where
+    F: FnOnce() -> R,
+{
+    if edge == DebounceEdge::Trailing {
+        return debounce(key, window_ms, f);
+    }
+
+    let window = Duration::from_millis(window_ms);
+    let now = Instant::now();
+
+    let mut state = LEADING_STATE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let last_call = state.insert(key.to_string(), now);
+    drop(state); // Release lock before execution
+
+    if let Some(last_call) = last_call
+        && now.duration_since(last_call) < window
+    {
+        warn!(key = %key, "🚫 Debounced - burst still in progress");
+        return None;
+    }
+
+    info!(key = %key, "✅ Executing leading-edge debounced function");
+    Some(f())
+}
+
+/// Leading-edge debounce that still executes at least once per `max_wait_ms`.
+///
+/// Like [`debounce_edge`] in [`DebounceEdge::Leading`] mode, calls are skipped
+/// while they keep arriving less than `window_ms` apart. Under continuous
+/// calls that would starve the key forever, so once `max_wait_ms` have passed
+/// since the first skipped call, the next call executes regardless.
+///
+/// # Arguments
+/// * `key` - Unique identifier for this debounce group
+/// * `window_ms` - Quiet period in milliseconds that ends a burst
+/// * `max_wait_ms` - Longest time a skipped call can go without an execution
+/// * `f` - The function to execute
+///
+/// # Returns
+/// `Some(R)` if executed, `None` if debounced
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(debounce_max_wait("search", 300, 2000))]
+/// fn update_suggestions() -> Option<()> {
+///     // Waits for typing to pause, but refreshes at least every 2s
+/// }
+/// ```
+pub fn debounce_max_wait<F, R>(key: &str, window_ms: u64, max_wait_ms: u64, f: F) -> Option<R>
+where
+    F: FnOnce() -> R,
+{
+    let window = Duration::from_millis(window_ms);
+    let max_wait = Duration::from_millis(max_wait_ms);
+    let now = Instant::now();
+
+    let mut state = MAX_WAIT_STATE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let (in_burst, overdue) = state.get(key).map_or((false, false), |entry| {
+        (
+            now.duration_since(entry.last_call) < window,
+            entry
+                .first_suppressed_at
+                .is_some_and(|first| now.duration_since(first) >= max_wait),
+        )
+    });
+    let entry = state.entry(key.to_string()).or_insert(MaxWaitState {
+        last_call: now,
+        first_suppressed_at: None,
+    });
+    entry.last_call = now;
+
+    if in_burst && !overdue {
+        entry.first_suppressed_at.get_or_insert(now);
+        warn!(key = %key, "🚫 Debounced - burst still in progress");
+        return None;
+    }
+
+    entry.first_suppressed_at = None;
+    drop(state); // Release lock before execution
+
+    if in_burst {
+        info!(key = %key, max_wait_ms = %max_wait_ms, "⏰ Max wait reached, executing");
+    } else {
+        info!(key = %key, "✅ Executing debounced function");
+    }
+    Some(f())
+}
+
 /// Debounces with a default value returned when debounced.
 ///
 /// # Arguments
@@ -74,12 +222,75 @@ where
     debounce(key, window_ms, f).unwrap_or(default)
 }
 
+/// Runs a function at most once per interval, for periodic side effects.
+///
+/// The first call runs immediately; later calls run once the interval since
+/// the previous run's due time has elapsed, so a steadily called function keeps
+/// a fixed cadence instead of drifting by each call's lateness. After a long
+/// pause, the schedule restarts from the next run rather than catching up.
+/// [`reset_debounce`] lets the next call for `key` run immediately.
+///
+/// # Arguments
+/// * `key` - Unique identifier for this periodic task
+/// * `interval_ms` - Minimum milliseconds between runs
+/// * `f` - The function to execute
+///
+/// # Returns
+/// `Some(R)` if executed, `None` if skipped
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(once_per_interval("metrics_flush", 10_000))]
+/// fn flush_metrics() -> Option<usize> {
+///     // Runs at most every 10 seconds, however often it is called
+/// }
+/// ```
+pub fn once_per_interval<F, R>(key: &str, interval_ms: u64, f: F) -> Option<R>
+where
+    F: FnOnce() -> R,
+{
+    let interval = Duration::from_millis(interval_ms);
+    let now = Instant::now();
+
+    let mut state = INTERVAL_STATE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let due = state.get(key).copied();
+    if let Some(due) = due
+        && now < due
+    {
+        return None;
+    }
+
+    // Keep the cadence of the previous slot unless a whole interval was missed
+    let next_due = match due {
+        Some(due) if due + interval > now => due + interval,
+        _ => now + interval,
+    };
+    state.insert(key.to_string(), next_due);
+    drop(state); // Release lock before execution
+
+    info!(key = %key, interval_ms = %interval_ms, "⏰ Running periodic function");
+    Some(f())
+}
+
 /// Resets the debounce state for a key, allowing immediate execution.
 pub fn reset_debounce(key: &str) {
     if let Ok(mut state) = DEBOUNCE_STATE.lock() {
         state.remove(key);
         info!(key = %key, "🔄 Debounce state reset");
     }
+    if let Ok(mut state) = LEADING_STATE.lock() {
+        state.remove(key);
+    }
+    if let Ok(mut state) = MAX_WAIT_STATE.lock() {
+        state.remove(key);
+    }
+    if let Ok(mut state) = INTERVAL_STATE.lock() {
+        state.remove(key);
+    }
 }
 
 /// Clears all debounce state.
@@ -88,4 +299,13 @@ pub fn clear_all_debounce() {
         state.clear();
         info!("🔄 All debounce state cleared");
     }
+    if let Ok(mut state) = LEADING_STATE.lock() {
+        state.clear();
+    }
+    if let Ok(mut state) = MAX_WAIT_STATE.lock() {
+        state.clear();
+    }
+    if let Ok(mut state) = INTERVAL_STATE.lock() {
+        state.clear();
+    }
 }
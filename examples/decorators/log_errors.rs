@@ -1,8 +1,36 @@
 //! Error logging decorator for Result-returning functions.
 
+use std::collections::HashMap;
 use std::panic::Location;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
 use tracing::{error, info, warn};
 
+/// How long an error suppresses identical ones in [`log_errors_dedup`].
+const DEDUP_WINDOW: Duration = Duration::from_secs(10);
+
+/// The last error logged from a [`log_errors_dedup`] call site.
+struct LastError {
+    message: String,
+    logged_at: Instant,
+    suppressed: u64,
+}
+
+static LAST_ERRORS: LazyLock<Mutex<HashMap<&'static Location<'static>, LastError>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// How long a sampling window lasts in [`log_errors_sampled`].
+const SAMPLE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Errors counted at a [`log_errors_sampled`] call site in the current window.
+struct ErrorSample {
+    window_start: Instant,
+    errors: u64,
+}
+
+static ERROR_SAMPLES: LazyLock<Mutex<HashMap<&'static Location<'static>, ErrorSample>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
 /// Logs errors from Result-returning functions without modifying the result.
 ///
 /// # Features
@@ -131,6 +159,54 @@ where
     }
 }
 
+/// Logs errors with context like [`log_errors_with_context`], returning the original error.
+///
+/// The error is logged as `context: error` but returned unchanged, so callers
+/// can still match on its variants or downcast it.
+///
+/// # Arguments
+/// * `context` - Additional context to include in the log
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(log_errors_context_typed("loading config"))]
+/// fn load_config(path: &Path) -> Result<Config, ConfigError> {
+///     // `ConfigError::NotFound` still reaches the caller as is
+/// }
+/// ```
+#[track_caller]
+pub fn log_errors_context_typed<F, R, E>(context: &str, f: F) -> Result<R, E>
+where
+    F: FnOnce() -> Result<R, E>,
+    E: std::fmt::Debug + std::fmt::Display,
+{
+    let location = Location::caller();
+
+    let result = f();
+
+    match &result {
+        Ok(_) => {
+            info!(
+                context = %context,
+                "✅ Operation succeeded"
+            );
+        }
+        Err(e) => {
+            error!(
+                context = %context,
+                file = %location.file(),
+                line = %location.line(),
+                original_error = ?e,
+                "❌ {}: {}", context, e
+            );
+        }
+    }
+
+    result
+}
+
 /// Logs warnings for recoverable errors, errors for fatal ones.
 ///
 /// # Arguments
@@ -172,3 +248,142 @@ where
 
     result
 }
+
+/// Logs errors, suppressing repeats of the previous error to avoid flooding logs.
+///
+/// An error whose `Debug` output matches the last one logged from the same
+/// call site within 10 seconds is not logged again. Instead, a single
+/// "N identical errors suppressed" summary is logged once a different error or
+/// a success arrives, or when the same error recurs after the window, which
+/// then starts a new window.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(log_errors_dedup)]
+/// fn poll_queue() -> Result<Vec<Message>, QueueError> {
+///     // A broker outage logs once, not on every poll
+/// }
+/// ```
+#[track_caller]
+pub fn log_errors_dedup<F, R, E>(f: F) -> Result<R, E>
+where
+    F: FnOnce() -> Result<R, E>,
+    E: std::fmt::Debug,
+{
+    let location = Location::caller();
+
+    let result = f();
+
+    let mut last_errors = LAST_ERRORS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let message = result.as_ref().err().map(|e| format!("{:?}", e));
+    if let (Some(message), Some(last)) = (&message, last_errors.get_mut(location))
+        && *message == last.message
+        && last.logged_at.elapsed() < DEDUP_WINDOW
+    {
+        last.suppressed += 1;
+        return result;
+    }
+
+    if let Some(last) = last_errors.remove(location)
+        && last.suppressed > 0
+    {
+        warn!(
+            file = %location.file(),
+            line = %location.line(),
+            error = %last.message,
+            suppressed = %last.suppressed,
+            "🔁 {} identical errors suppressed", last.suppressed
+        );
+    }
+
+    if let Some(message) = message {
+        error!(
+            file = %location.file(),
+            line = %location.line(),
+            error = %message,
+            "❌ Operation failed"
+        );
+        last_errors.insert(
+            location,
+            LastError {
+                message,
+                logged_at: Instant::now(),
+                suppressed: 0,
+            },
+        );
+    }
+
+    result
+}
+
+/// Logs only a fraction of errors, so a failing downstream doesn't flood the logs.
+///
+/// Errors are counted per call site in 60 second windows. The first error of a
+/// window is always logged; after that, one in every `1 / sample_rate` errors
+/// is, so `0.1` logs about one error in ten. Logged errors include the number
+/// of errors seen so far in the window.
+///
+/// # Arguments
+/// * `sample_rate` - Fraction of errors to log, clamped to `0.0..=1.0`
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(log_errors_sampled(0.1))]
+/// fn send_event(event: &Event) -> Result<(), HttpError> {
+///     // While the collector is down, about 10% of failures are logged
+/// }
+/// ```
+#[track_caller]
+pub fn log_errors_sampled<F, R, E>(sample_rate: f64, f: F) -> Result<R, E>
+where
+    F: FnOnce() -> Result<R, E>,
+    E: std::fmt::Debug,
+{
+    let location = Location::caller();
+
+    let result = f();
+
+    let Err(e) = &result else {
+        return result;
+    };
+
+    let errors = {
+        let mut samples = ERROR_SAMPLES
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let sample = samples.entry(location).or_insert_with(|| ErrorSample {
+            window_start: Instant::now(),
+            errors: 0,
+        });
+        if sample.window_start.elapsed() >= SAMPLE_WINDOW {
+            sample.window_start = Instant::now();
+            sample.errors = 0;
+        }
+        sample.errors += 1;
+        sample.errors
+    };
+
+    // Log whenever the running count of sampled errors goes up
+    let sample_rate = sample_rate.clamp(0.0, 1.0);
+    let sampled = (errors as f64 * sample_rate) as u64;
+    let sampled_before = ((errors - 1) as f64 * sample_rate) as u64;
+
+    if errors == 1 || sampled > sampled_before {
+        error!(
+            file = %location.file(),
+            line = %location.line(),
+            error = ?e,
+            errors_in_window = %errors,
+            sample_rate = %sample_rate,
+            "❌ Operation failed"
+        );
+    }
+
+    result
+}
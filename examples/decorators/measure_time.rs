@@ -1,8 +1,12 @@
 //! Performance measurement decorator with detailed metrics.
 
+use hdrhistogram::Histogram;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::panic::Location;
-use std::time::Instant;
-use tracing::{Level, info, warn};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{Level, debug, info, warn};
 
 /// Measures and logs execution time of a function.
 ///
@@ -107,3 +111,239 @@ where
 
     result
 }
+
+/// Measures execution time, staying at DEBUG unless the call is slower than `warn_above`.
+///
+/// Unlike [`measure_time`], which logs every call at INFO, this keeps hot paths
+/// quiet and only surfaces slow calls as a WARN with the elapsed time and the
+/// threshold.
+///
+/// # Arguments
+/// * `warn_above` - Durations longer than this are logged as warnings
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(measure_time_threshold(Duration::from_millis(50)))]
+/// fn lookup_price(sku: &str) -> Price {
+///     // Only lookups slower than 50ms show up in the logs
+/// }
+/// ```
+#[track_caller]
+pub fn measure_time_threshold<F, R>(warn_above: Duration, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let location = Location::caller();
+    let file = location
+        .file()
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(location.file());
+    let line = location.line();
+
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+
+    if elapsed > warn_above {
+        warn!(
+            target: "perf",
+            file = %file,
+            line = %line,
+            duration_ms = %elapsed.as_millis(),
+            threshold_ms = %warn_above.as_millis(),
+            "⚠️  Slow call: {:?} exceeded {:?}",
+            elapsed,
+            warn_above
+        );
+    } else {
+        debug!(
+            target: "perf",
+            file = %file,
+            line = %line,
+            duration_us = %elapsed.as_micros(),
+            "⏱️  Completed in {:?}",
+            elapsed
+        );
+    }
+
+    result
+}
+
+thread_local! {
+    /// Time spent in nested exclusive calls, one accumulator per active call.
+    static EXCLUSIVE_STACK: RefCell<Vec<Duration>> = const { RefCell::new(Vec::new()) };
+}
+
+static EXCLUSIVE_TIMES: LazyLock<Mutex<HashMap<String, Duration>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Pops the current accumulator even if the measured function panics.
+struct ExclusiveFrame;
+
+impl Drop for ExclusiveFrame {
+    fn drop(&mut self) {
+        EXCLUSIVE_STACK.with(|stack| stack.borrow_mut().pop());
+    }
+}
+
+/// Measures self-time, excluding time spent in nested `measure_time_exclusive` calls.
+///
+/// When decorated functions call each other, inclusive timing counts the inner
+/// function's time twice. This reports and accumulates only the time spent in
+/// the function itself, retrievable with [`get_exclusive_time`].
+///
+/// # Arguments
+/// * `name` - Name under which the self-time is recorded
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(measure_time_exclusive("render_page"))]
+/// fn render_page() -> Html {
+///     render_header() // time spent here is attributed to render_header
+/// }
+/// ```
+pub fn measure_time_exclusive<F, R>(name: &str, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    EXCLUSIVE_STACK.with(|stack| stack.borrow_mut().push(Duration::ZERO));
+    let frame = ExclusiveFrame;
+
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+
+    let nested = EXCLUSIVE_STACK.with(|stack| stack.borrow().last().copied().unwrap_or_default());
+    drop(frame);
+
+    // Attribute this call's inclusive time to the enclosing call, if any
+    EXCLUSIVE_STACK.with(|stack| {
+        if let Some(parent) = stack.borrow_mut().last_mut() {
+            *parent += elapsed;
+        }
+    });
+
+    let exclusive = elapsed.saturating_sub(nested);
+    *EXCLUSIVE_TIMES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .entry(name.to_string())
+        .or_default() += exclusive;
+
+    info!(
+        target: "perf",
+        name = %name,
+        exclusive_us = %exclusive.as_micros(),
+        inclusive_us = %elapsed.as_micros(),
+        "⏱️  Self time {:?}",
+        exclusive
+    );
+
+    result
+}
+
+/// Gets the total self-time recorded for a name by [`measure_time_exclusive`].
+pub fn get_exclusive_time(name: &str) -> Option<Duration> {
+    EXCLUSIVE_TIMES
+        .lock()
+        .ok()
+        .and_then(|times| times.get(name).copied())
+}
+
+/// Longest recorded duration, one hour; slower calls are recorded as this.
+const MAX_TRACKED_LATENCY_US: u64 = 3_600_000_000;
+
+static LATENCIES: LazyLock<Mutex<HashMap<String, Histogram<u64>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Latency statistics recorded by [`measure_time_named`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencySnapshot {
+    /// Number of recorded calls
+    pub count: u64,
+    pub min: Duration,
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+/// Measures and logs execution time like [`measure_time`], recording it in a histogram.
+///
+/// Durations are recorded per `name` in an HDR histogram with microsecond
+/// resolution, up to one hour, so [`get_latency_stats`] can report percentiles over every call
+/// so far.
+///
+/// # Arguments
+/// * `name` - Name under which the durations are recorded
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(measure_time_named("checkout"))]
+/// fn checkout(cart: &Cart) -> Order {
+///     // ...
+/// }
+///
+/// let p99 = get_latency_stats("checkout").map(|stats| stats.p99);
+/// ```
+#[track_caller]
+pub fn measure_time_named<F, R>(name: &str, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let location = Location::caller();
+    let file = location
+        .file()
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(location.file());
+    let line = location.line();
+
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+
+    LATENCIES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .entry(name.to_string())
+        .or_insert_with(|| {
+            Histogram::new_with_bounds(1, MAX_TRACKED_LATENCY_US, 3)
+                .expect("histogram bounds are valid")
+        })
+        .saturating_record(elapsed.as_micros() as u64);
+
+    info!(
+        target: "perf",
+        name = %name,
+        file = %file,
+        line = %line,
+        duration_us = %elapsed.as_micros(),
+        "⏱️  Completed in {:?}",
+        elapsed
+    );
+
+    result
+}
+
+/// Gets latency statistics for a name recorded by [`measure_time_named`].
+pub fn get_latency_stats(name: &str) -> Option<LatencySnapshot> {
+    let latencies = LATENCIES.lock().ok()?;
+    let histogram = latencies.get(name)?;
+
+    Some(LatencySnapshot {
+        count: histogram.len(),
+        min: Duration::from_micros(histogram.min()),
+        mean: Duration::from_micros(histogram.mean() as u64),
+        p50: Duration::from_micros(histogram.value_at_quantile(0.5)),
+        p99: Duration::from_micros(histogram.value_at_quantile(0.99)),
+        max: Duration::from_micros(histogram.max()),
+    })
+}
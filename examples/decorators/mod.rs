@@ -4,8 +4,9 @@
 //!
 //! - **Observability**: `measure_time`, `trace_calls`, `log_errors`
 //! - **Resilience**: `with_retry`, `with_backoff`, `with_timeout`, `circuit_breaker`
-//! - **Performance**: `with_cache`, `rate_limit`, `debounce`
+//! - **Performance**: `with_cache`, `rate_limit`, `debounce`, `with_semaphore`
 //! - **Safety**: `safe_decorator`, `validate_input`
+//! - **Composition**: `DecorateConfig`, a runtime mirror of the attribute options
 //!
 //! # Example
 //!
@@ -20,6 +21,7 @@
 //! ```
 
 mod circuit_breaker;
+mod compose;
 mod debounce;
 mod log_errors;
 mod measure_time;
@@ -30,17 +32,69 @@ mod validate;
 mod with_backoff;
 mod with_cache;
 mod with_retry;
+mod with_semaphore;
 mod with_timeout;
 
-pub use circuit_breaker::{CircuitState, circuit_breaker};
-pub use debounce::debounce;
-pub use log_errors::log_errors;
-pub use measure_time::measure_time;
-pub use rate_limit::rate_limit;
-pub use safe_decorator::safe_decorator;
-pub use trace_calls::trace_calls;
-pub use validate::validate_input;
-pub use with_backoff::with_backoff;
-pub use with_cache::{CacheStats, get_cache_stats, with_cache};
-pub use with_retry::with_retry;
-pub use with_timeout::with_timeout;
+pub use circuit_breaker::{
+    CircuitState, circuit_breaker, circuit_breaker_fallback, circuit_breaker_if,
+    circuit_breaker_rate, circuit_breaker_rate_based, get_circuit_state, list_circuits,
+    on_circuit_transition, reset_all_circuits, reset_circuit,
+};
+pub use compose::{DecorateConfig, DecorateConfigBuilder};
+pub use debounce::{
+    DebounceEdge, clear_all_debounce, debounce, debounce_edge, debounce_max_wait,
+    debounce_with_default, once_per_interval, reset_debounce,
+};
+pub use log_errors::{
+    log_errors, log_errors_classified, log_errors_context_typed, log_errors_dedup,
+    log_errors_named, log_errors_sampled, log_errors_with_context,
+};
+pub use measure_time::{
+    LatencySnapshot, get_exclusive_time, get_latency_stats, measure_time, measure_time_exclusive,
+    measure_time_named, measure_time_threshold, measure_time_with_threshold,
+};
+pub use rate_limit::{
+    get_rate_limit_stats, rate_limit, rate_limit_keyed, rate_limit_token_bucket, rate_limit_try,
+    reset_rate_limit,
+};
+pub use safe_decorator::{
+    SafeResult, install_panic_logger, safe_decorator, safe_to_result, safe_with_context,
+    safe_with_default, safe_with_fallback,
+};
+#[cfg(unix)]
+pub use trace_calls::{get_profile_folded, trace_calls_profile};
+pub use trace_calls::{
+    trace_calls, trace_calls_events, trace_calls_max_depth, trace_calls_named, trace_calls_otel,
+    trace_calls_with,
+};
+#[cfg(feature = "tokio")]
+pub use validate::{AsyncValidationRule, CheckFuture, validate_input_async};
+pub use validate::{
+    ValidationRule, number_rules, require_some, string_rules, validate_input, validate_input_all,
+    validate_input_typed,
+};
+pub use with_backoff::{
+    BackoffConfig, BackoffStats, get_backoff_stats, with_backoff, with_backoff_config,
+    with_backoff_if, with_backoff_jitter, with_backoff_jitter_using, with_backoff_metered,
+    with_backoff_verbose, with_backoff_verbose_using, with_decorrelated_jitter,
+};
+pub use with_cache::cache_key_from;
+pub use with_cache::{
+    Cache, CacheStats, EvictionPolicy, cache_contains, cache_entry_age, cache_entry_ttl,
+    cache_lock_acquisitions, cache_metrics_text, clear_cache, get_cache_stats, invalidate_cache,
+    invalidate_cache_prefix, pin_cache_entry, set_cache_eviction_policy, set_cache_max_size,
+    set_refresh_ahead, unpin_cache_entry, with_cache, with_cache_forever, with_cache_jittered,
+    with_cache_refreshable, with_cache_tiered,
+};
+pub use with_retry::{
+    RetryConfig, try_retry, with_retry, with_retry_all_errors, with_retry_config,
+    with_retry_on_panic, with_retry_result, with_retry_result_config, with_retry_robust,
+    with_retry_robust_using,
+};
+pub use with_semaphore::{with_semaphore, with_semaphore_fair};
+#[cfg(feature = "tokio")]
+pub use with_timeout::with_timeout_async;
+pub use with_timeout::{
+    TimeoutError, TimeoutStats, get_timeout_stats, with_timeout, with_timeout_cancellable,
+    with_timeout_metered, with_timeout_result, with_timeout_scoped,
+};
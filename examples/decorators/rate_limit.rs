@@ -109,9 +109,74 @@ where
     f()
 }
 
+/// Rate limits without blocking, returning the remaining cooldown when called too soon.
+///
+/// Shares state with [`rate_limit_keyed`] for the same key. A rejected call does
+/// not run `f` and does not count as a request.
+///
+/// # Arguments
+/// * `key` - Unique identifier for this rate limit group
+/// * `delay_ms` - Minimum milliseconds between executions
+/// * `f` - The function to execute
+///
+/// # Returns
+/// `Ok(R)` if the call was allowed, `Err(remaining)` with the time left until the
+/// next call is allowed otherwise
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(rate_limit_try("refresh", 5000))]
+/// fn refresh_dashboard() -> Dashboard {
+///     // Callers get Err(remaining) instead of blocking
+/// }
+/// ```
+pub fn rate_limit_try<F, R>(key: &str, delay_ms: u64, f: F) -> Result<R, Duration>
+where
+    F: FnOnce() -> R,
+{
+    let delay = Duration::from_millis(delay_ms);
+    let now = Instant::now();
+
+    {
+        let mut limiters = RATE_LIMITERS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let state = limiters
+            .entry(key.to_string())
+            .or_insert_with(|| RateLimiterState {
+                last_request: now - delay, // Allow immediate first request
+                request_count: 0,
+            });
+
+        // `last_request` may lie in the future if a blocking caller is sleeping
+        let ready_at = state.last_request + delay;
+        if now < ready_at {
+            let remaining = ready_at - now;
+            warn!(
+                key = %key,
+                remaining_ms = %remaining.as_millis(),
+                "🚫 Rate limited - rejecting call"
+            );
+            return Err(remaining);
+        }
+
+        state.last_request = now;
+        state.request_count += 1;
+    }
+
+    info!(key = %key, "✅ Rate limit passed");
+    Ok(f())
+}
+
 /// Token bucket rate limiter for burst-tolerant rate limiting.
 ///
 /// Allows bursts up to `bucket_size` requests, then enforces the rate limit.
+/// Each key has an independent bucket, so different endpoints keep separate
+/// budgets. A key starts with a full bucket of `bucket_size` tokens, every call
+/// spends one token, and tokens refill continuously at `tokens_per_second`. The
+/// size and rate are fixed by the first call for a key.
 ///
 /// # Arguments
 /// * `key` - Unique identifier for this rate limit group
@@ -121,6 +186,20 @@ where
 ///
 /// # Returns
 /// `Some(R)` if a token was available, `None` if rate limited
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(rate_limit_token_bucket("search", 2.0, 10))]
+/// fn search(query: &str) -> Option<Results> {
+///     // ...
+/// }
+///
+/// #[decorate(rate_limit_token_bucket("upload", 0.1, 2))]
+/// fn upload(file: File) -> Option<Receipt> {
+///     // ...
+/// }
+/// ```
 pub fn rate_limit_token_bucket<F, R>(
     key: &str,
     tokens_per_second: f64,
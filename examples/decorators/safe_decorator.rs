@@ -187,7 +187,7 @@ where
 }
 
 /// Extracts a human-readable message from a panic payload.
-fn extract_panic_message(payload: &Box<dyn Any + Send>) -> String {
+pub(crate) fn extract_panic_message(payload: &Box<dyn Any + Send>) -> String {
     if let Some(s) = payload.downcast_ref::<&str>() {
         s.to_string()
     } else if let Some(s) = payload.downcast_ref::<String>() {
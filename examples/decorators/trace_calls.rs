@@ -1,8 +1,16 @@
 //! Function call tracing decorator for debugging and observability.
 
+use std::cell::Cell;
+#[cfg(unix)]
+use std::collections::HashMap;
+use std::fmt::Display;
 use std::panic::Location;
 use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(unix)]
+use std::sync::{LazyLock, Mutex};
 use std::time::Instant;
+#[cfg(unix)]
+use tracing::warn;
 use tracing::{Level, info, span};
 
 static CALL_ID: AtomicU64 = AtomicU64::new(0);
@@ -108,3 +116,328 @@ where
 
     result
 }
+
+/// Traces function calls like [`trace_calls`], attaching key/value attributes to the span.
+///
+/// Span field names must be known at compile time, so the pairs are recorded
+/// together in a single `attrs` field formatted as `key=value` separated by
+/// spaces. Arguments of the decorated function are in scope for the decorator
+/// arguments, which makes request identifiers easy to attach.
+///
+/// # Arguments
+/// * `attrs` - Key/value pairs recorded on the span
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(trace_calls_with(&[("order_id", &order_id), ("user_id", &user_id)]))]
+/// fn process_order(order_id: u64, user_id: u64) -> Result<(), Error> {
+///     // Span fields: call_id=1 file=orders.rs line=12 attrs="order_id=42 user_id=7"
+/// }
+/// ```
+#[track_caller]
+pub fn trace_calls_with<F, R>(attrs: &[(&str, &dyn Display)], f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let call_id = CALL_ID.fetch_add(1, Ordering::Relaxed);
+    let location = Location::caller();
+    let attrs = attrs
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let span = span!(
+        Level::INFO,
+        "fn_call",
+        call_id = %call_id,
+        file = %location.file(),
+        line = %location.line(),
+        attrs = %attrs
+    );
+    let _guard = span.enter();
+
+    info!(call_id = %call_id, "→ Entering function");
+
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+
+    info!(
+        call_id = %call_id,
+        duration_ms = %elapsed.as_millis(),
+        "← Exiting function"
+    );
+
+    result
+}
+
+/// Traces function calls like [`trace_calls`] as a child of the current OpenTelemetry context.
+///
+/// A `fn_call` span is started from the global tracer provider with the current
+/// context as its parent, so the call joins an existing distributed trace
+/// instead of starting a new root. The `call_id` and caller location become span
+/// attributes, and the span's context is current while `f` runs.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(trace_calls_otel)]
+/// fn charge_card(order_id: u64) -> Result<Receipt, Error> {
+///     // Exported as a child of the span that was current at the call site
+/// }
+/// ```
+#[track_caller]
+pub fn trace_calls_otel<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    use opentelemetry::trace::{TraceContextExt, Tracer};
+    use opentelemetry::{Context, KeyValue, global};
+
+    let call_id = CALL_ID.fetch_add(1, Ordering::Relaxed);
+    let location = Location::caller();
+
+    let tracer = global::tracer("decorate_macro");
+    let span = tracer
+        .span_builder("fn_call")
+        .with_attributes([
+            KeyValue::new("call_id", call_id as i64),
+            KeyValue::new("code.file.path", location.file()),
+            KeyValue::new("code.line.number", i64::from(location.line())),
+        ])
+        .start_with_context(&tracer, &Context::current());
+    let context = Context::current_with_span(span);
+    let _attached = context.clone().attach();
+
+    info!(call_id = %call_id, "→ Entering function");
+
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+
+    info!(
+        call_id = %call_id,
+        duration_ms = %elapsed.as_millis(),
+        "← Exiting function"
+    );
+
+    context.span().end();
+    result
+}
+
+thread_local! {
+    static TRACE_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Restores the trace depth when a traced call returns or unwinds.
+struct DepthGuard;
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        TRACE_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+/// Traces function calls like [`trace_calls`], up to a maximum nesting depth.
+///
+/// Depth counts nested `trace_calls_max_depth` calls on the current thread.
+/// Calls nested deeper than `max` run the function without creating a span or
+/// logging, which keeps deeply recursive functions from producing huge span
+/// trees.
+///
+/// # Arguments
+/// * `max` - Number of nested calls that are traced
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(trace_calls_max_depth(3))]
+/// fn walk(node: &Node) -> usize {
+///     // Only the top three levels of the recursion are traced
+///     1 + node.children.iter().map(walk).sum::<usize>()
+/// }
+/// ```
+#[track_caller]
+pub fn trace_calls_max_depth<F, R>(max: usize, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let depth = TRACE_DEPTH.with(|depth| {
+        depth.set(depth.get() + 1);
+        depth.get()
+    });
+    let _depth = DepthGuard;
+
+    if depth > max {
+        return f();
+    }
+
+    let call_id = CALL_ID.fetch_add(1, Ordering::Relaxed);
+    let span = span!(Level::INFO, "fn_call", call_id = %call_id, depth = %depth);
+    let _guard = span.enter();
+
+    info!(call_id = %call_id, depth = %depth, "→ Entering function");
+
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+
+    info!(
+        call_id = %call_id,
+        duration_ms = %elapsed.as_millis(),
+        "← Exiting function"
+    );
+
+    result
+}
+
+/// Traces function calls as a flat pair of `fn.enter` and `fn.exit` events.
+///
+/// Unlike [`trace_calls`], no span is created and both events are emitted at the
+/// top level, even inside an active span. They share a `call_id` field, so
+/// log pipelines that consume flat event streams can still correlate them.
+///
+/// # Arguments
+/// * `function` - Name recorded in the `function` field of both events
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(trace_calls_events("process_order"))]
+/// fn process_order(order_id: u64) -> Result<(), Error> {
+///     // Emits `fn.enter` and `fn.exit` events with the same call_id
+/// }
+/// ```
+#[track_caller]
+pub fn trace_calls_events<F, R>(function: &str, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let call_id = CALL_ID.fetch_add(1, Ordering::Relaxed);
+    let location = Location::caller();
+
+    // `target` is spelled out since tracing's `name` + `parent` form fails to compile
+    info!(
+        name: "fn.enter",
+        target: module_path!(),
+        parent: None,
+        call_id = call_id,
+        function = %function,
+        file = %location.file(),
+        line = location.line(),
+        "→ Entering function"
+    );
+
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+
+    info!(
+        name: "fn.exit",
+        target: module_path!(),
+        parent: None,
+        call_id = call_id,
+        function = %function,
+        duration_ms = elapsed.as_millis() as u64,
+        "← Exiting function"
+    );
+
+    result
+}
+
+#[cfg(unix)]
+static PROFILES: LazyLock<Mutex<HashMap<String, Vec<String>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Traces a function call while sampling call stacks for flamegraph generation.
+///
+/// Samples are collected with `pprof` at 1 kHz for the duration of the call and
+/// stored per `name` in the folded-stack format (`root;child;leaf count`) consumed
+/// by `flamegraph.pl` and `inferno-flamegraph`.
+///
+/// Only one profiling session can be active per process; concurrent calls run
+/// the function without sampling.
+///
+/// # Arguments
+/// * `name` - Name under which the samples are stored
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(trace_calls_profile("render"))]
+/// fn render_page() -> Html {
+///     // ...
+/// }
+///
+/// std::fs::write("render.folded", get_profile_folded("render").unwrap())?;
+/// ```
+#[cfg(unix)]
+#[track_caller]
+pub fn trace_calls_profile<F, R>(name: &str, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let guard = match pprof::ProfilerGuard::new(1000) {
+        Ok(guard) => guard,
+        Err(e) => {
+            warn!(name = %name, error = %e, "⚠️ Profiler unavailable, running unprofiled");
+            return trace_calls_named(name, f);
+        }
+    };
+
+    let result = trace_calls_named(name, f);
+
+    match guard.report().build() {
+        Ok(report) => {
+            let folded: Vec<String> = report
+                .data
+                .iter()
+                .map(|(frames, count)| {
+                    let stack: Vec<String> = frames
+                        .frames
+                        .iter()
+                        .rev()
+                        .flat_map(|symbols| symbols.iter().rev().map(|s| s.name()))
+                        .collect();
+                    format!(
+                        "{};{} {}",
+                        frames.thread_name_or_id(),
+                        stack.join(";"),
+                        count
+                    )
+                })
+                .collect();
+
+            info!(
+                name = %name,
+                samples = %folded.len(),
+                "🔥 Recorded profile samples"
+            );
+
+            PROFILES
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .entry(name.to_string())
+                .or_default()
+                .extend(folded);
+        }
+        Err(e) => warn!(name = %name, error = %e, "⚠️ Failed to build profile report"),
+    }
+
+    result
+}
+
+/// Gets the recorded samples for a profiled name in folded-stack format.
+#[cfg(unix)]
+pub fn get_profile_folded(name: &str) -> Option<String> {
+    PROFILES
+        .lock()
+        .ok()
+        .and_then(|profiles| profiles.get(name).map(|lines| lines.join("\n")))
+}
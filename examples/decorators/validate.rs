@@ -42,6 +42,35 @@ impl<T> ValidationRule<T> {
 pub fn validate_input<T, F, R>(input: &T, rules: &[ValidationRule<T>], f: F) -> Result<R, String>
 where
     F: FnOnce() -> Result<R, String>,
+{
+    validate_input_typed(input, rules, str::to_string, f)
+}
+
+/// Validates input like [`validate_input`], wrapping a failed rule's message
+/// into a domain error with `make_err`.
+///
+/// # Arguments
+/// * `input` - The value to validate
+/// * `rules` - Slice of validation rules to apply
+/// * `make_err` - Converts the failing rule's message into the error type
+/// * `f` - The function to execute if validation passes
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(validate_input_typed(id, ID_RULES, ApiError::InvalidInput))]
+/// fn get_user(id: u64) -> Result<User, ApiError> {
+///     // ...
+/// }
+/// ```
+pub fn validate_input_typed<T, F, R, E>(
+    input: &T,
+    rules: &[ValidationRule<T>],
+    make_err: impl Fn(&str) -> E,
+    f: F,
+) -> Result<R, E>
+where
+    F: FnOnce() -> Result<R, E>,
 {
     info!("🔍 Validating input against {} rules", rules.len());
 
@@ -52,7 +81,7 @@ where
                 message = %rule.message,
                 "❌ Validation failed"
             );
-            return Err(rule.message.to_string());
+            return Err(make_err(rule.message));
         }
     }
 
@@ -60,6 +89,142 @@ where
     f()
 }
 
+/// Validates input against every rule, reporting all violations at once.
+///
+/// Unlike [`validate_input`], a failing rule doesn't stop the others from
+/// running, so e.g. a form can show every problem in one pass.
+///
+/// # Arguments
+/// * `input` - The value to validate
+/// * `rules` - Slice of validation rules to apply
+/// * `f` - The function to execute if validation passes
+///
+/// # Returns
+/// `Ok(R)` if every rule passes and the function succeeds, otherwise `Err` with
+/// the failing rules' messages in rule order
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(validate_input_all(&username, USERNAME_RULES))]
+/// fn register(username: String) -> Result<User, Vec<String>> {
+///     // ...
+/// }
+/// ```
+pub fn validate_input_all<T, F, R>(
+    input: &T,
+    rules: &[ValidationRule<T>],
+    f: F,
+) -> Result<R, Vec<String>>
+where
+    F: FnOnce() -> Result<R, Vec<String>>,
+{
+    info!("🔍 Validating input against {} rules", rules.len());
+
+    let violations: Vec<String> = rules
+        .iter()
+        .filter(|rule| !(rule.check)(input))
+        .map(|rule| rule.message.to_string())
+        .collect();
+
+    if !violations.is_empty() {
+        error!(
+            failed = %violations.len(),
+            messages = ?violations,
+            "❌ Validation failed"
+        );
+        return Err(violations);
+    }
+
+    info!("✅ All validations passed");
+    f()
+}
+
+/// Future returned by an [`AsyncValidationRule`]'s check.
+#[cfg(feature = "tokio")]
+pub type CheckFuture<'a> = std::pin::Pin<Box<dyn std::future::Future<Output = bool> + Send + 'a>>;
+
+/// Boxed async predicate of an [`AsyncValidationRule`].
+#[cfg(feature = "tokio")]
+type AsyncCheck<T> = Box<dyn for<'a> Fn(&'a T) -> CheckFuture<'a> + Send + Sync>;
+
+/// Validation rule whose check is async, e.g. to look a value up in a database.
+#[cfg(feature = "tokio")]
+pub struct AsyncValidationRule<T> {
+    /// The validation predicate
+    pub check: AsyncCheck<T>,
+    /// Error message if validation fails
+    pub message: &'static str,
+}
+
+#[cfg(feature = "tokio")]
+impl<T> AsyncValidationRule<T> {
+    /// Creates a rule from a predicate returning a boxed future, such as
+    /// `|name| Box::pin(async move { !is_taken(name).await })`.
+    pub fn new<C>(check: C, message: &'static str) -> Self
+    where
+        C: for<'a> Fn(&'a T) -> CheckFuture<'a> + Send + Sync + 'static,
+    {
+        Self {
+            check: Box::new(check),
+            message,
+        }
+    }
+}
+
+/// Validates input against async rules before awaiting the function.
+///
+/// Rules are awaited one at a time, in order, and the first failure is
+/// returned without running the remaining rules or the function. Requires the
+/// `tokio` feature.
+///
+/// # Arguments
+/// * `input` - The value to validate
+/// * `rules` - Slice of async validation rules to apply
+/// * `f` - The function to execute if validation passes
+///
+/// # Example
+///
+/// ```rust,ignore
+/// static USERNAME_RULES: LazyLock<Vec<AsyncValidationRule<String>>> = LazyLock::new(|| {
+///     vec![AsyncValidationRule::<String>::new(
+///         |name| Box::pin(async move { !db::username_exists(name).await }),
+///         "Username is already taken",
+///     )]
+/// });
+///
+/// #[decorate(validate_input_async(&username, &USERNAME_RULES))]
+/// async fn register(username: String) -> Result<User, String> {
+///     // ...
+/// }
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn validate_input_async<T, F, Fut, R>(
+    input: &T,
+    rules: &[AsyncValidationRule<T>],
+    f: F,
+) -> Result<R, String>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Result<R, String>>,
+{
+    info!("🔍 Validating input against {} async rules", rules.len());
+
+    for (i, rule) in rules.iter().enumerate() {
+        if !(rule.check)(input).await {
+            error!(
+                rule_index = %i,
+                message = %rule.message,
+                "❌ Validation failed"
+            );
+            return Err(rule.message.to_string());
+        }
+    }
+
+    info!("✅ All validations passed");
+    f().await
+}
+
 /// Common validation rules for strings.
 pub mod string_rules {
     use super::ValidationRule;
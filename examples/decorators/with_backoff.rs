@@ -1,5 +1,9 @@
 //! Exponential backoff decorator for resilient operations.
 
+use super::with_retry::random_unit;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 use tracing::{error, info, warn};
@@ -120,6 +124,344 @@ where
     unreachable!()
 }
 
+/// Exponential backoff that retries only errors accepted by `should_retry`.
+///
+/// A rejected error is returned immediately without sleeping, like an HTTP
+/// client that retries 5xx responses but not 4xx. Delays follow [`with_backoff`].
+///
+/// # Arguments
+/// * `max_attempts` - Maximum number of attempts
+/// * `initial_delay` - Initial delay before first retry
+/// * `should_retry` - Predicate deciding whether an error is transient
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(with_backoff_if(5, Duration::from_millis(100), |e: &HttpError| e.status >= 500))]
+/// fn fetch_page() -> Result<Page, HttpError> {
+///     // ...
+/// }
+/// ```
+pub fn with_backoff_if<F, R, E, P>(
+    max_attempts: u32,
+    initial_delay: Duration,
+    should_retry: P,
+    f: F,
+) -> Result<R, E>
+where
+    F: Fn() -> Result<R, E>,
+    E: std::fmt::Debug,
+    P: Fn(&E) -> bool,
+{
+    let config = BackoffConfig {
+        max_attempts,
+        initial_delay,
+        ..Default::default()
+    };
+
+    for attempt in 1..=config.max_attempts.max(1) {
+        match f() {
+            Ok(result) => return Ok(result),
+            Err(e) if !should_retry(&e) => {
+                error!(attempt = %attempt, error = ?e, "❌ Non-retryable error");
+                return Err(e);
+            }
+            Err(e) if attempt >= config.max_attempts => {
+                error!(attempts = %attempt, error = ?e, "❌ All {} attempts failed", attempt);
+                return Err(e);
+            }
+            Err(e) => {
+                let delay = calculate_backoff_delay(
+                    attempt,
+                    config.initial_delay,
+                    config.max_delay,
+                    config.base,
+                );
+                warn!(
+                    attempt = %attempt,
+                    max_attempts = %config.max_attempts,
+                    error = ?e,
+                    delay_ms = %delay.as_millis(),
+                    "❌ Attempt {}/{} failed, backing off",
+                    attempt,
+                    config.max_attempts
+                );
+                thread::sleep(delay);
+            }
+        }
+    }
+
+    unreachable!()
+}
+
+/// Exponential backoff with a capped delay and randomized sleeps.
+///
+/// The delay before retry `n` is `initial_delay * 2^(n-1)`, capped at
+/// `max_delay`, then scaled by a random factor in
+/// `[1 - jitter_fraction, 1 + jitter_fraction]` so that many callers failing
+/// together do not retry in lockstep. Jittered delays never exceed `max_delay`.
+///
+/// # Arguments
+/// * `max_attempts` - Maximum number of attempts
+/// * `initial_delay` - Delay before the first retry, before jitter
+/// * `max_delay` - Upper bound for any delay
+/// * `jitter_fraction` - Relative jitter, clamped to `0.0..=1.0`
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(with_backoff_jitter(5, Duration::from_millis(100), Duration::from_secs(2), 0.2))]
+/// fn call_external_service() -> Result<Response, Error> {
+///     // Retries after roughly 100ms, 200ms, 400ms, 800ms, each ±20%
+/// }
+/// ```
+pub fn with_backoff_jitter<F, R, E>(
+    max_attempts: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    jitter_fraction: f64,
+    f: F,
+) -> Result<R, E>
+where
+    F: Fn() -> Result<R, E>,
+    E: std::fmt::Debug,
+{
+    with_backoff_jitter_using(
+        max_attempts,
+        initial_delay,
+        max_delay,
+        jitter_fraction,
+        thread::sleep,
+        random_unit,
+        f,
+    )
+}
+
+/// Like [`with_backoff_jitter`], with injectable sleeping and randomness.
+///
+/// `random` must return values in `0.0..1.0`; `0.5` leaves a delay unjittered.
+pub fn with_backoff_jitter_using<F, R, E, S, J>(
+    max_attempts: u32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    jitter_fraction: f64,
+    mut sleep: S,
+    mut random: J,
+    f: F,
+) -> Result<R, E>
+where
+    F: Fn() -> Result<R, E>,
+    E: std::fmt::Debug,
+    S: FnMut(Duration),
+    J: FnMut() -> f64,
+{
+    let jitter_fraction = jitter_fraction.clamp(0.0, 1.0);
+
+    for attempt in 1..=max_attempts.max(1) {
+        match f() {
+            Ok(result) => return Ok(result),
+            Err(e) if attempt >= max_attempts => {
+                error!(attempts = %attempt, error = ?e, "❌ All {} attempts failed", attempt);
+                return Err(e);
+            }
+            Err(e) => {
+                let base = calculate_backoff_delay(attempt, initial_delay, max_delay, 2.0);
+                let factor = 1.0 + jitter_fraction * (2.0 * random().clamp(0.0, 1.0) - 1.0);
+                let delay = base.mul_f64(factor).min(max_delay);
+
+                warn!(
+                    attempt = %attempt,
+                    max_attempts = %max_attempts,
+                    error = ?e,
+                    delay_ms = %delay.as_millis(),
+                    "❌ Attempt {}/{} failed, backing off",
+                    attempt,
+                    max_attempts
+                );
+                sleep(delay);
+            }
+        }
+    }
+
+    unreachable!()
+}
+
+/// Exponential backoff that logs the total time spent sleeping between attempts.
+///
+/// Delays follow [`with_backoff`]. On final success or failure, the sum of all
+/// backoff sleeps is logged as `total_delay_ms`, which shows the wall-clock cost
+/// of the retries rather than only each individual delay.
+///
+/// # Arguments
+/// * `max_attempts` - Maximum number of attempts
+/// * `initial_delay` - Initial delay before first retry
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(with_backoff_verbose(5, Duration::from_millis(100)))]
+/// fn sync_inventory() -> Result<(), Error> {
+///     // Logs e.g. "total_delay_ms=300" after succeeding on the third attempt
+/// }
+/// ```
+pub fn with_backoff_verbose<F, R, E>(
+    max_attempts: u32,
+    initial_delay: Duration,
+    f: F,
+) -> Result<R, E>
+where
+    F: Fn() -> Result<R, E>,
+    E: std::fmt::Debug,
+{
+    with_backoff_verbose_using(max_attempts, initial_delay, thread::sleep, f)
+}
+
+/// Like [`with_backoff_verbose`], with injectable sleeping.
+pub fn with_backoff_verbose_using<F, R, E, S>(
+    max_attempts: u32,
+    initial_delay: Duration,
+    mut sleep: S,
+    f: F,
+) -> Result<R, E>
+where
+    F: Fn() -> Result<R, E>,
+    E: std::fmt::Debug,
+    S: FnMut(Duration),
+{
+    let config = BackoffConfig {
+        max_attempts,
+        initial_delay,
+        ..Default::default()
+    };
+    let mut total_delay = Duration::ZERO;
+
+    for attempt in 1..=config.max_attempts.max(1) {
+        match f() {
+            Ok(result) => {
+                info!(
+                    attempt = %attempt,
+                    total_delay_ms = total_delay.as_millis() as u64,
+                    "✅ Succeeded after {} attempts, {:?} spent backing off",
+                    attempt,
+                    total_delay
+                );
+                return Ok(result);
+            }
+            Err(e) if attempt >= config.max_attempts => {
+                error!(
+                    attempts = %attempt,
+                    error = ?e,
+                    total_delay_ms = total_delay.as_millis() as u64,
+                    "❌ All {} attempts failed, {:?} spent backing off",
+                    attempt,
+                    total_delay
+                );
+                return Err(e);
+            }
+            Err(e) => {
+                let delay = calculate_backoff_delay(
+                    attempt,
+                    config.initial_delay,
+                    config.max_delay,
+                    config.base,
+                );
+                warn!(
+                    attempt = %attempt,
+                    error = ?e,
+                    delay_ms = %delay.as_millis(),
+                    "❌ Attempt {}/{} failed, backing off",
+                    attempt,
+                    config.max_attempts
+                );
+                sleep(delay);
+                total_delay += delay;
+            }
+        }
+    }
+
+    unreachable!()
+}
+
+/// Attempt statistics recorded by [`with_backoff_metered`].
+#[derive(Debug, Clone, Default)]
+pub struct BackoffStats {
+    /// Number of decorated calls
+    pub total_calls: u64,
+    /// Number of attempts across all calls
+    pub total_attempts: u64,
+    /// Attempt number on which each successful call succeeded
+    pub succeeded_on: Vec<u32>,
+}
+
+static BACKOFF_STATS: LazyLock<Mutex<HashMap<String, BackoffStats>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Exponential backoff that records per-name attempt statistics.
+///
+/// Behaves like [`with_backoff`] and additionally records how many attempts each
+/// call needed, retrievable with [`get_backoff_stats`].
+///
+/// # Arguments
+/// * `name` - Name under which the statistics are recorded
+/// * `max_attempts` - Maximum number of attempts
+/// * `initial_delay` - Initial delay before first retry
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(with_backoff_metered("payments", 5, Duration::from_millis(100)))]
+/// fn charge_card() -> Result<Receipt, Error> {
+///     // ...
+/// }
+/// ```
+pub fn with_backoff_metered<F, R, E>(
+    name: &str,
+    max_attempts: u32,
+    initial_delay: Duration,
+    f: F,
+) -> Result<R, E>
+where
+    F: Fn() -> Result<R, E>,
+    E: std::fmt::Debug,
+{
+    let attempts = Cell::new(0u32);
+    let result = with_backoff(max_attempts, initial_delay, || {
+        attempts.set(attempts.get() + 1);
+        f()
+    });
+
+    let mut stats = BACKOFF_STATS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let entry = stats.entry(name.to_string()).or_default();
+    entry.total_calls += 1;
+    entry.total_attempts += attempts.get() as u64;
+    if result.is_ok() {
+        entry.succeeded_on.push(attempts.get());
+    }
+
+    info!(
+        name = %name,
+        attempts = %attempts.get(),
+        succeeded = %result.is_ok(),
+        "📊 Backoff attempts recorded"
+    );
+
+    result
+}
+
+/// Gets the attempt statistics recorded for a name.
+pub fn get_backoff_stats(name: &str) -> Option<BackoffStats> {
+    BACKOFF_STATS
+        .lock()
+        .ok()
+        .and_then(|stats| stats.get(name).cloned())
+}
+
 /// Calculates the backoff delay for a given attempt.
 fn calculate_backoff_delay(
     attempt: u32,
@@ -1,8 +1,17 @@
 //! Caching decorator with TTL and eviction support.
+//!
+//! Hits, misses and the cache size are also reported live through the
+//! `metrics` crate as `cache_hits`, `cache_misses` and `cache_size`.
 
+use super::with_retry::random_unit;
 use std::any::Any;
-use std::collections::HashMap;
-use std::sync::{LazyLock, RwLock};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{
+    Arc, Condvar, LazyLock, LockResult, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
+use std::thread;
 use std::time::{Duration, Instant};
 use tracing::{info, warn};
 
@@ -12,6 +21,9 @@ struct CacheEntry {
     created_at: Instant,
     last_accessed: Instant,
     access_count: u64,
+    pinned: bool,
+    /// TTL fixed when the entry was stored, overriding the one given on lookup
+    ttl: Option<Duration>,
 }
 
 /// Cache statistics.
@@ -20,6 +32,7 @@ pub struct CacheStats {
     pub hits: u64,
     pub misses: u64,
     pub evictions: u64,
+    pub expirations: u64,
     pub size: usize,
 }
 
@@ -34,24 +47,228 @@ impl CacheStats {
     }
 }
 
+/// Which entry is evicted when the cache is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    /// Evict the least recently used entry
+    #[default]
+    Lru,
+    /// Evict the least frequently used entry, the least recently used among ties
+    Lfu,
+}
+
 type CacheMap = HashMap<String, CacheEntry>;
 
 struct CacheState {
     entries: CacheMap,
     stats: CacheStats,
     max_size: usize,
+    eviction_policy: EvictionPolicy,
+    refresh_ahead: HashMap<String, f64>,
+    refreshing: HashSet<String>,
+}
+
+/// A computation in progress for one key; `true` once it has finished.
+type Flight = Arc<(Mutex<bool>, Condvar)>;
+
+/// An independent cache namespace.
+///
+/// The free functions such as [`with_cache`] share one process-wide instance,
+/// where equal keys from unrelated modules collide. Subsystems that want their
+/// own keys, size limit, eviction policy and statistics can construct a `Cache`
+/// instead.
+///
+/// Values are stored type-erased rather than as `Cache<K, V>`, because the
+/// shared instance behind [`with_cache`] holds the results of every decorated
+/// function whatever their type. Each lookup checks the stored type, and reading
+/// a key with a different type than it was cached with logs a warning and is
+/// treated as a miss.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// static USERS: LazyLock<Cache> = LazyLock::new(|| Cache::new(10_000));
+///
+/// fn fetch_user(id: u64) -> Result<User, Error> {
+///     // Cached in USERS only, unaffected by other modules using the same key
+///     USERS.get_or_compute(&id.to_string(), Duration::from_secs(300), || load_user(id))
+/// }
+/// ```
+pub struct Cache {
+    state: RwLock<CacheState>,
+    /// Keys whose values are being computed, so concurrent misses wait instead
+    /// of recomputing
+    in_flight: Mutex<HashMap<String, Flight>>,
+    /// Number of times `state` has been locked, to observe contention
+    lock_acquisitions: AtomicU64,
+}
+
+impl Cache {
+    /// Creates an empty cache holding at most `max_size` entries.
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            state: RwLock::new(CacheState {
+                entries: HashMap::new(),
+                stats: CacheStats::default(),
+                max_size,
+                eviction_policy: EvictionPolicy::Lru,
+                refresh_ahead: HashMap::new(),
+                refreshing: HashSet::new(),
+            }),
+            in_flight: Mutex::new(HashMap::new()),
+            lock_acquisitions: AtomicU64::new(0),
+        }
+    }
+
+    fn read_state(&self) -> LockResult<RwLockReadGuard<'_, CacheState>> {
+        self.lock_acquisitions.fetch_add(1, Ordering::Relaxed);
+        self.state.read()
+    }
+
+    fn write_state(&self) -> LockResult<RwLockWriteGuard<'_, CacheState>> {
+        self.lock_acquisitions.fetch_add(1, Ordering::Relaxed);
+        self.state.write()
+    }
+
+    /// Returns the value cached under `key`, or computes and caches it with `f`.
+    ///
+    /// Behaves like [`with_cache`], scoped to this cache.
+    pub fn get_or_compute<F, T, E>(&self, key: &str, ttl: Duration, f: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+        T: Clone + Send + Sync + 'static,
+        E: std::fmt::Debug,
+    {
+        cache_for(self, key, Some(ttl), f)
+    }
+
+    /// Returns whether a value is cached under `key`; see [`cache_contains`].
+    pub fn contains(&self, key: &str) -> bool {
+        self.read_state()
+            .is_ok_and(|cache| cache.entries.contains_key(key))
+    }
+
+    /// Removes the entry under `key`.
+    pub fn invalidate(&self, key: &str) {
+        if let Ok(mut cache) = self.write_state()
+            && cache.entries.remove(key).is_some()
+        {
+            cache.stats.size = cache.entries.len();
+            info!(key = %key, "🗑️ Cache entry invalidated");
+        }
+    }
+
+    /// Sets which entry is evicted when this cache is full.
+    ///
+    /// Applies to all later evictions; existing entries keep their access history.
+    pub fn set_eviction_policy(&self, policy: EvictionPolicy) {
+        if let Ok(mut cache) = self.write_state() {
+            cache.eviction_policy = policy;
+            info!(policy = ?policy, "📊 Cache eviction policy updated");
+        }
+    }
+
+    /// Pins the entry under `key` so it is never evicted; see [`pin_cache_entry`].
+    pub fn pin(&self, key: &str) -> bool {
+        self.set_pinned(key, true)
+    }
+
+    /// Unpins the entry under `key`, making it eligible for eviction again.
+    pub fn unpin(&self, key: &str) -> bool {
+        self.set_pinned(key, false)
+    }
+
+    fn set_pinned(&self, key: &str, pinned: bool) -> bool {
+        let mut cache = self.write_state().unwrap_or_else(|p| p.into_inner());
+        match cache.entries.get_mut(key) {
+            Some(entry) => {
+                entry.pinned = pinned;
+                info!(key = %key, pinned = %pinned, "📌 Cache entry pin updated");
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes every entry.
+    pub fn clear(&self) {
+        if let Ok(mut cache) = self.write_state() {
+            let count = cache.entries.len();
+            cache.entries.clear();
+            cache.stats.size = 0;
+            info!(count = %count, "🗑️ Cache cleared");
+        }
+    }
+
+    /// Returns how many times this cache's shared lock has been taken.
+    pub fn lock_acquisitions(&self) -> u64 {
+        self.lock_acquisitions.load(Ordering::Relaxed)
+    }
+
+    /// Gets this cache's statistics.
+    pub fn stats(&self) -> CacheStats {
+        self.read_state()
+            .map(|cache| cache.stats.clone())
+            .unwrap_or_default()
+    }
 }
 
-static CACHE: LazyLock<RwLock<CacheState>> = LazyLock::new(|| {
-    RwLock::new(CacheState {
-        entries: HashMap::new(),
-        stats: CacheStats::default(),
-        max_size: 1000,
-    })
-});
+/// The cache shared by the free functions in this module.
+static CACHE: LazyLock<Cache> = LazyLock::new(|| Cache::new(1000));
+
+/// Held by the thread computing a key; dropping it wakes the waiting threads,
+/// including when the computation fails or panics.
+struct FlightGuard<'a> {
+    cache: &'a Cache,
+    key: &'a str,
+    flight: Flight,
+}
+
+impl Drop for FlightGuard<'_> {
+    fn drop(&mut self) {
+        self.cache
+            .in_flight
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .remove(self.key);
+
+        let (done, finished) = &*self.flight;
+        *done.lock().unwrap_or_else(|p| p.into_inner()) = true;
+        finished.notify_all();
+    }
+}
+
+/// Claims the computation of `key`, or waits for the thread that already has.
+///
+/// Returns the guard when this thread should compute the value, and `None`
+/// after another thread's computation has finished.
+fn join_flight<'a>(cache: &'a Cache, key: &'a str) -> Option<FlightGuard<'a>> {
+    let flight = {
+        let mut in_flight = cache.in_flight.lock().unwrap_or_else(|p| p.into_inner());
+        match in_flight.get(key) {
+            Some(flight) => Arc::clone(flight),
+            None => {
+                let flight = Flight::default();
+                in_flight.insert(key.to_string(), Arc::clone(&flight));
+                return Some(FlightGuard { cache, key, flight });
+            }
+        }
+    };
+
+    info!(key = %key, "⏳ Waiting for in-flight computation");
+    let (done, finished) = &*flight;
+    let mut done = done.lock().unwrap_or_else(|p| p.into_inner());
+    while !*done {
+        done = finished.wait(done).unwrap_or_else(|p| p.into_inner());
+    }
+    None
+}
 
 /// Caches the result of a function with a TTL.
 ///
+/// Concurrent misses on the same key run `f` only once: the other callers wait
+/// for that result instead of recomputing it. If it fails, one waiter retries.
+///
 /// # Arguments
 /// * `cache_key` - Unique key for this cached value
 /// * `ttl` - Time-to-live for the cached value
@@ -71,79 +288,204 @@ where
     T: Clone + Send + Sync + 'static,
     E: std::fmt::Debug,
 {
-    let start = Instant::now();
+    CACHE.get_or_compute(cache_key, ttl, f)
+}
 
-    // Try to get from cache (read lock)
-    {
-        let cache = CACHE.read().unwrap_or_else(|p| p.into_inner());
-
-        if let Some(entry) = cache.entries.get(cache_key) {
-            if entry.created_at.elapsed() < ttl {
-                if let Some(value) = entry.value.downcast_ref::<T>() {
-                    info!(
-                        key = %cache_key,
-                        age_ms = %entry.created_at.elapsed().as_millis(),
-                        access_count = %entry.access_count,
-                        latency_us = %start.elapsed().as_micros(),
-                        "💾 Cache hit"
-                    );
-
-                    // Update access stats (need write lock, but return value first)
-                    let cloned = value.clone();
-                    drop(cache);
-
-                    // Update stats
-                    if let Ok(mut cache) = CACHE.write() {
-                        cache.stats.hits += 1;
-                        if let Some(entry) = cache.entries.get_mut(cache_key) {
-                            entry.last_accessed = Instant::now();
-                            entry.access_count += 1;
-                        }
-                    }
-
-                    return Ok(cloned);
-                }
-            } else {
-                info!(
-                    key = %cache_key,
-                    age_ms = %entry.created_at.elapsed().as_millis(),
-                    ttl_ms = %ttl.as_millis(),
-                    "🔄 Cache expired"
-                );
+/// Caches the result of a function without expiry.
+///
+/// The entry stays fresh regardless of age and is only removed by explicit
+/// invalidation or eviction when the cache is full.
+///
+/// # Arguments
+/// * `cache_key` - Unique key for this cached value
+/// * `f` - The function to execute on cache miss
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(with_cache_forever("country_codes"))]
+/// fn load_country_codes() -> Result<Vec<String>, Error> {
+///     // Loaded once, kept until invalidated
+/// }
+/// ```
+pub fn with_cache_forever<F, T, E>(cache_key: &str, f: F) -> Result<T, E>
+where
+    F: FnOnce() -> Result<T, E>,
+    T: Clone + Send + Sync + 'static,
+    E: std::fmt::Debug,
+{
+    cache_for(&CACHE, cache_key, None, f)
+}
+
+/// How long a value stays in the per-thread tier of [`with_cache_tiered`].
+const L1_TTL: Duration = Duration::from_secs(1);
+
+/// Maximum number of values in each thread's tier of [`with_cache_tiered`].
+const L1_MAX_SIZE: usize = 64;
+
+/// Per-thread tier of [`with_cache_tiered`]: values and when they were stored.
+type L1Map = HashMap<String, (Box<dyn Any>, Instant)>;
+
+thread_local! {
+    static L1_CACHE: RefCell<L1Map> = RefCell::new(HashMap::new());
+}
+
+/// Caches like [`with_cache`], with a small lock-free per-thread tier in front.
+///
+/// Each thread first checks its own cache (L1), then the shared cache (L2),
+/// and only then runs `f`. L1 hits never take the shared lock. To stay
+/// coherent with L2, L1 entries live for at most one second (or `ttl`, if
+/// shorter), so an invalidation may take that long to reach every thread.
+///
+/// # Arguments
+/// * `cache_key` - Unique key for this cached value
+/// * `ttl` - Time-to-live for the value in the shared cache
+/// * `f` - The function to execute on a miss in both tiers
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(with_cache_tiered("feature_flags", Duration::from_secs(30)))]
+/// fn load_feature_flags() -> Result<Flags, Error> {
+///     // Read on every request; mostly served without any locking
+/// }
+/// ```
+pub fn with_cache_tiered<F, T, E>(cache_key: &str, ttl: Duration, f: F) -> Result<T, E>
+where
+    F: FnOnce() -> Result<T, E>,
+    T: Clone + Send + Sync + 'static,
+    E: std::fmt::Debug,
+{
+    let l1_ttl = ttl.min(L1_TTL);
+    let l1_hit = L1_CACHE.with_borrow_mut(|l1| {
+        match l1.get(cache_key) {
+            Some((value, stored_at)) if stored_at.elapsed() < l1_ttl => {
+                return value.downcast_ref::<T>().cloned();
             }
-        } else {
-            info!(key = %cache_key, "🔍 Cache miss");
+            Some(_) => {
+                l1.remove(cache_key);
+            }
+            None => {}
         }
+        None
+    });
+    if let Some(value) = l1_hit {
+        info!(key = %cache_key, "⚡ L1 cache hit");
+        return Ok(value);
     }
 
+    let result = CACHE.get_or_compute(cache_key, ttl, f);
+
+    if let Ok(ref value) = result {
+        L1_CACHE.with_borrow_mut(|l1| {
+            if !l1.contains_key(cache_key) && l1.len() >= L1_MAX_SIZE {
+                l1.retain(|_, (_, stored_at)| stored_at.elapsed() < L1_TTL);
+                if l1.len() >= L1_MAX_SIZE {
+                    l1.clear();
+                }
+            }
+            l1.insert(
+                cache_key.to_string(),
+                (Box::new(value.clone()), Instant::now()),
+            );
+        });
+    }
+
+    result
+}
+
+/// Caches like [`with_cache`], randomizing each entry's TTL to spread out expiry.
+///
+/// Entries cached together with the same TTL would otherwise all expire at
+/// once and be recomputed in a burst. Here each newly stored entry gets its own
+/// TTL, drawn uniformly from `ttl ± jitter` and kept for the entry's lifetime.
+///
+/// # Arguments
+/// * `cache_key` - Unique key for this cached value
+/// * `ttl` - Base time-to-live for the cached value
+/// * `jitter` - Maximum deviation from `ttl`, in either direction
+/// * `f` - The function to execute on cache miss
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(with_cache_jittered(&format!("product:{id}"), Duration::from_secs(300), Duration::from_secs(30)))]
+/// fn fetch_product(id: u64) -> Result<Product, Error> {
+///     // Entries expire between 4.5 and 5.5 minutes after being cached
+/// }
+/// ```
+pub fn with_cache_jittered<F, T, E>(
+    cache_key: &str,
+    ttl: Duration,
+    jitter: Duration,
+    f: F,
+) -> Result<T, E>
+where
+    F: FnOnce() -> Result<T, E>,
+    T: Clone + Send + Sync + 'static,
+    E: std::fmt::Debug,
+{
+    let offset = 2.0 * random_unit() - 1.0;
+    let entry_ttl = if offset >= 0.0 {
+        ttl + jitter.mul_f64(offset)
+    } else {
+        ttl.saturating_sub(jitter.mul_f64(-offset))
+    };
+    cache_for_entry(&CACHE, cache_key, Some(ttl), Some(entry_ttl), f)
+}
+
+/// Caches with an optional TTL, where `None` never expires.
+fn cache_for<F, T, E>(cache: &Cache, cache_key: &str, ttl: Option<Duration>, f: F) -> Result<T, E>
+where
+    F: FnOnce() -> Result<T, E>,
+    T: Clone + Send + Sync + 'static,
+    E: std::fmt::Debug,
+{
+    cache_for_entry(cache, cache_key, ttl, None, f)
+}
+
+/// Like [`cache_for`], storing a new entry with its own TTL if `entry_ttl` is set.
+fn cache_for_entry<F, T, E>(
+    cache: &Cache,
+    cache_key: &str,
+    ttl: Option<Duration>,
+    entry_ttl: Option<Duration>,
+    f: F,
+) -> Result<T, E>
+where
+    F: FnOnce() -> Result<T, E>,
+    T: Clone + Send + Sync + 'static,
+    E: std::fmt::Debug,
+{
+    let start = Instant::now();
+
+    // Waiters look the key up again once the computing thread is done
+    let _flight = loop {
+        if let Some((value, _)) = lookup::<T>(cache, cache_key, ttl, start) {
+            return Ok(value);
+        }
+        if let Some(flight) = join_flight(cache, cache_key) {
+            break flight;
+        }
+    };
+
     // Cache miss - execute function
     let result = f();
 
     // Store in cache on success
     if let Ok(ref value) = result {
-        let mut cache = CACHE.write().unwrap_or_else(|p| p.into_inner());
-        cache.stats.misses += 1;
+        metrics::counter!("cache_misses").increment(1);
 
-        // Evict if at capacity
-        if cache.entries.len() >= cache.max_size {
-            evict_lru(&mut cache);
+        let mut cache = cache.write_state().unwrap_or_else(|p| p.into_inner());
+        cache.stats.misses += 1;
+        store_entry(&mut cache, cache_key, value.clone());
+        if let Some(entry) = cache.entries.get_mut(cache_key) {
+            entry.ttl = entry_ttl;
         }
 
-        let now = Instant::now();
-        cache.entries.insert(
-            cache_key.to_string(),
-            CacheEntry {
-                value: Box::new(value.clone()),
-                created_at: now,
-                last_accessed: now,
-                access_count: 1,
-            },
-        );
-        cache.stats.size = cache.entries.len();
-
         info!(
             key = %cache_key,
-            ttl_ms = %ttl.as_millis(),
+            ttl = ?entry_ttl.or(ttl),
             cache_size = %cache.entries.len(),
             latency_ms = %start.elapsed().as_millis(),
             "📝 Cached result"
@@ -153,33 +495,275 @@ where
     result
 }
 
-/// Evicts the least recently used entry.
-fn evict_lru(cache: &mut CacheState) {
-    if let Some((key, _)) = cache
+/// Caches like [`with_cache`], refreshing hot entries in the background.
+///
+/// When a key has a refresh-ahead ratio (see [`set_refresh_ahead`]) and a hit
+/// finds the entry older than `ratio * ttl`, `f` is run on a background thread
+/// to replace the entry while the current value is served immediately. At most
+/// one refresh per key runs at a time.
+///
+/// # Arguments
+/// * `cache_key` - Unique key for this cached value
+/// * `ttl` - Time-to-live for the cached value
+/// * `f` - The function to execute on cache miss or refresh
+///
+/// # Example
+///
+/// ```rust,ignore
+/// set_refresh_ahead("exchange_rates", 0.8);
+///
+/// #[decorate(with_cache_refreshable("exchange_rates", Duration::from_secs(60)))]
+/// fn fetch_exchange_rates() -> Result<Rates, Error> {
+///     // Refreshed in the background after 48s, so callers never wait
+/// }
+/// ```
+pub fn with_cache_refreshable<F, T, E>(cache_key: &str, ttl: Duration, f: F) -> Result<T, E>
+where
+    F: FnOnce() -> Result<T, E> + Send + 'static,
+    T: Clone + Send + Sync + 'static,
+    E: std::fmt::Debug,
+{
+    let start = Instant::now();
+
+    let Some((value, age)) = lookup::<T>(&CACHE, cache_key, Some(ttl), start) else {
+        return with_cache(cache_key, ttl, f);
+    };
+
+    let should_refresh = {
+        let mut cache = CACHE.write_state().unwrap_or_else(|p| p.into_inner());
+        match cache.refresh_ahead.get(cache_key) {
+            Some(&ratio) if age >= ttl.mul_f64(ratio) => {
+                cache.refreshing.insert(cache_key.to_string())
+            }
+            _ => false,
+        }
+    };
+
+    if should_refresh {
+        info!(
+            key = %cache_key,
+            age_ms = %age.as_millis(),
+            ttl_ms = %ttl.as_millis(),
+            "🔁 Refreshing cache entry ahead of expiry"
+        );
+
+        let key = cache_key.to_string();
+        thread::spawn(move || {
+            let result = f();
+
+            let mut cache = CACHE.write_state().unwrap_or_else(|p| p.into_inner());
+            cache.refreshing.remove(&key);
+            match result {
+                Ok(value) => {
+                    store_entry(&mut cache, &key, value);
+                    info!(key = %key, "📝 Cache entry refreshed");
+                }
+                Err(e) => warn!(key = %key, error = ?e, "⚠️ Cache refresh failed"),
+            }
+        });
+    }
+
+    Ok(value)
+}
+
+/// Enables refresh-ahead for a key used with [`with_cache_refreshable`].
+///
+/// `ratio` is the fraction of the TTL (between 0.0 and 1.0) after which a hit
+/// triggers a background refresh.
+pub fn set_refresh_ahead(key: &str, ratio: f64) {
+    let mut cache = CACHE.write_state().unwrap_or_else(|p| p.into_inner());
+    cache
+        .refresh_ahead
+        .insert(key.to_string(), ratio.clamp(0.0, 1.0));
+    info!(key = %key, ratio = %ratio, "🔁 Refresh-ahead enabled");
+}
+
+/// Builds a deterministic cache key from a serializable value.
+///
+/// Use this for arguments that don't implement `Display`, such as query structs.
+/// The key is the value's JSON encoding, so equal values map to the same key as
+/// long as their serialization is stable (struct fields always are; `HashMap`
+/// iteration order is not).
+///
+/// # Panics
+/// Panics if the value cannot be serialized, e.g. a map with non-string keys.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(with_cache(&cache_key_from(&query), Duration::from_secs(60)))]
+/// fn search(query: SearchQuery) -> Result<Vec<Hit>, Error> {
+///     // Cached separately for each distinct query
+/// }
+/// ```
+pub fn cache_key_from<K: serde::Serialize>(k: &K) -> String {
+    serde_json::to_string(k).expect("cache key value must be serializable")
+}
+
+/// Looks up a fresh entry, recording the hit and returning the value and its age.
+///
+/// A `ttl` of `None` means the entry never expires.
+fn lookup<T>(
+    cache: &Cache,
+    cache_key: &str,
+    ttl: Option<Duration>,
+    start: Instant,
+) -> Option<(T, Duration)>
+where
+    T: Clone + 'static,
+{
+    let state = cache;
+    let cache = state.read_state().unwrap_or_else(|p| p.into_inner());
+
+    let Some(entry) = cache.entries.get(cache_key) else {
+        info!(key = %cache_key, "🔍 Cache miss");
+        return None;
+    };
+
+    let age = entry.created_at.elapsed();
+    if let Some(ttl) = entry.ttl.or(ttl)
+        && age >= ttl
+    {
+        info!(
+            key = %cache_key,
+            age_ms = %age.as_millis(),
+            ttl_ms = %ttl.as_millis(),
+            "🔄 Cache expired"
+        );
+        drop(cache);
+
+        if let Ok(mut cache) = state.write_state() {
+            cache.stats.expirations += 1;
+        }
+        return None;
+    }
+
+    let Some(value) = entry.value.downcast_ref::<T>() else {
+        warn!(
+            key = %cache_key,
+            expected = %std::any::type_name::<T>(),
+            "⚠️ Cached value has a different type, treating as a miss"
+        );
+        return None;
+    };
+    info!(
+        key = %cache_key,
+        age_ms = %age.as_millis(),
+        access_count = %entry.access_count,
+        latency_us = %start.elapsed().as_micros(),
+        "💾 Cache hit"
+    );
+
+    // Update access stats (need write lock, but return value first)
+    let cloned = value.clone();
+    drop(cache);
+
+    metrics::counter!("cache_hits").increment(1);
+
+    if let Ok(mut cache) = state.write_state() {
+        cache.stats.hits += 1;
+        if let Some(entry) = cache.entries.get_mut(cache_key) {
+            entry.last_accessed = Instant::now();
+            entry.access_count += 1;
+        }
+    }
+
+    Some((cloned, age))
+}
+
+/// Inserts or replaces an entry, evicting to make room if needed.
+fn store_entry<T>(cache: &mut CacheState, cache_key: &str, value: T)
+where
+    T: Send + Sync + 'static,
+{
+    // Re-caching an expired pinned entry keeps it pinned
+    let pinned = cache
         .entries
-        .iter()
-        .min_by_key(|(_, entry)| entry.last_accessed)
-        .map(|(k, v)| (k.clone(), v.last_accessed))
+        .get(cache_key)
+        .is_some_and(|entry| entry.pinned);
+
+    // Evict if at capacity
+    if !cache.entries.contains_key(cache_key)
+        && cache.entries.len() >= cache.max_size
+        && !evict_entry(cache)
     {
+        warn!(
+            key = %cache_key,
+            max_size = %cache.max_size,
+            "⚠️ All entries pinned, exceeding cache capacity"
+        );
+    }
+
+    let now = Instant::now();
+    cache.entries.insert(
+        cache_key.to_string(),
+        CacheEntry {
+            value: Box::new(value),
+            created_at: now,
+            last_accessed: now,
+            access_count: 1,
+            pinned,
+            ttl: None,
+        },
+    );
+    cache.stats.size = cache.entries.len();
+
+    metrics::gauge!("cache_size").set(cache.stats.size as f64);
+}
+
+/// Evicts one unpinned entry, chosen by the cache's eviction policy.
+///
+/// Returns `false` if every entry is pinned and nothing could be evicted.
+fn evict_entry(cache: &mut CacheState) -> bool {
+    let policy = cache.eviction_policy;
+    let candidates = cache.entries.iter().filter(|(_, entry)| !entry.pinned);
+    let victim = match policy {
+        EvictionPolicy::Lru => candidates.min_by_key(|(_, entry)| entry.last_accessed),
+        EvictionPolicy::Lfu => {
+            candidates.min_by_key(|(_, entry)| (entry.access_count, entry.last_accessed))
+        }
+    };
+
+    if let Some(key) = victim.map(|(key, _)| key.clone()) {
         cache.entries.remove(&key);
         cache.stats.evictions += 1;
-        warn!(key = %key, "🗑️ Evicted LRU entry");
+        warn!(key = %key, policy = ?policy, "🗑️ Evicted cache entry");
+        true
+    } else {
+        false
     }
 }
 
+/// Sets which entry is evicted when the cache is full.
+///
+/// Applies to all later evictions; existing entries keep their access history.
+pub fn set_cache_eviction_policy(policy: EvictionPolicy) {
+    CACHE.set_eviction_policy(policy);
+}
+
+/// Pins a cache entry so it is never evicted to make room for other entries.
+///
+/// Pinned entries still expire according to their TTL and can be removed by
+/// explicit invalidation. Returns `false` if the key is not cached.
+pub fn pin_cache_entry(key: &str) -> bool {
+    CACHE.pin(key)
+}
+
+/// Unpins a cache entry, making it eligible for eviction again.
+///
+/// Returns `false` if the key is not cached.
+pub fn unpin_cache_entry(key: &str) -> bool {
+    CACHE.unpin(key)
+}
+
 /// Invalidates a specific cache entry.
 pub fn invalidate_cache(key: &str) {
-    if let Ok(mut cache) = CACHE.write()
-        && cache.entries.remove(key).is_some()
-    {
-        cache.stats.size = cache.entries.len();
-        info!(key = %key, "🗑️ Cache entry invalidated");
-    }
+    CACHE.invalidate(key);
 }
 
 /// Invalidates all cache entries matching a prefix.
 pub fn invalidate_cache_prefix(prefix: &str) {
-    if let Ok(mut cache) = CACHE.write() {
+    if let Ok(mut cache) = CACHE.write_state() {
         let keys_to_remove: Vec<_> = cache
             .entries
             .keys()
@@ -199,30 +783,122 @@ pub fn invalidate_cache_prefix(prefix: &str) {
 
 /// Clears the entire cache.
 pub fn clear_cache() {
-    if let Ok(mut cache) = CACHE.write() {
-        let count = cache.entries.len();
-        cache.entries.clear();
-        cache.stats.size = 0;
-        info!(count = %count, "🗑️ Cache cleared");
-    }
+    CACHE.clear();
+}
+
+/// Returns whether a value is cached under `key`.
+///
+/// Read-only: unlike a lookup, this neither counts as a hit or miss nor
+/// updates the entry's access time or count. TTLs are given per call, so an entry
+/// past its TTL is still reported until the next lookup replaces it.
+pub fn cache_contains(key: &str) -> bool {
+    CACHE.contains(key)
+}
+
+/// Returns how long ago the value under `key` was stored, without touching it.
+pub fn cache_entry_age(key: &str) -> Option<Duration> {
+    CACHE
+        .read_state()
+        .ok()?
+        .entries
+        .get(key)
+        .map(|entry| entry.created_at.elapsed())
+}
+
+/// Returns the TTL an entry was stored with, if it has its own.
+///
+/// Only entries cached by [`with_cache_jittered`] carry a TTL; others expire
+/// according to the TTL given on each lookup and return `None`.
+pub fn cache_entry_ttl(key: &str) -> Option<Duration> {
+    CACHE.read_state().ok()?.entries.get(key)?.ttl
 }
 
 /// Gets cache statistics.
 pub fn get_cache_stats() -> CacheStats {
-    CACHE
-        .read()
-        .map(|cache| cache.stats.clone())
-        .unwrap_or_default()
+    CACHE.stats()
+}
+
+/// Returns how many times the shared cache's lock has been taken.
+pub fn cache_lock_acquisitions() -> u64 {
+    CACHE.lock_acquisitions()
+}
+
+/// Renders the cache statistics in the Prometheus text exposition format.
+///
+/// Counters carry the `_total` suffix; the output can be served as-is from a
+/// `/metrics` endpoint.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// fn metrics_handler() -> String {
+///     cache_metrics_text()
+/// }
+/// ```
+pub fn cache_metrics_text() -> String {
+    let stats = get_cache_stats();
+    let metrics = [
+        (
+            "hits_total",
+            "counter",
+            "Cache lookups served from the cache.",
+            stats.hits as f64,
+        ),
+        (
+            "misses_total",
+            "counter",
+            "Cache lookups that ran the function.",
+            stats.misses as f64,
+        ),
+        (
+            "evictions_total",
+            "counter",
+            "Entries evicted to make room.",
+            stats.evictions as f64,
+        ),
+        (
+            "expirations_total",
+            "counter",
+            "Lookups that found an expired entry.",
+            stats.expirations as f64,
+        ),
+        (
+            "size",
+            "gauge",
+            "Entries currently cached.",
+            stats.size as f64,
+        ),
+        (
+            "hit_rate",
+            "gauge",
+            "Fraction of lookups that were hits.",
+            stats.hit_rate(),
+        ),
+    ];
+
+    metrics
+        .iter()
+        .map(|(name, kind, help, value)| {
+            format!(
+                "# HELP decorate_cache_{name} {help}\n\
+                 # TYPE decorate_cache_{name} {kind}\n\
+                 decorate_cache_{name} {value}\n"
+            )
+        })
+        .collect()
 }
 
 /// Sets the maximum cache size.
 pub fn set_cache_max_size(max_size: usize) {
-    if let Ok(mut cache) = CACHE.write() {
+    if let Ok(mut cache) = CACHE.write_state() {
         cache.max_size = max_size;
 
         // Evict if over new limit
         while cache.entries.len() > max_size {
-            evict_lru(&mut cache);
+            if !evict_entry(&mut cache) {
+                warn!(max_size = %max_size, "⚠️ All entries pinned, exceeding cache capacity");
+                break;
+            }
         }
 
         info!(max_size = %max_size, "📊 Cache max size updated");
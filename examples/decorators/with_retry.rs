@@ -1,5 +1,6 @@
 //! Retry decorator with configurable strategies.
 
+use super::safe_decorator::extract_panic_message;
 use std::thread;
 use std::time::{Duration, Instant};
 use tracing::{error, info, warn};
@@ -167,6 +168,79 @@ where
     );
 }
 
+/// Retries a function only on panics whose message satisfies `matches`.
+///
+/// Panics with a non-matching message are resumed immediately without further
+/// attempts, so only known-transient failures are retried.
+///
+/// # Arguments
+/// * `attempts` - Maximum number of attempts; `0` is treated as `1`
+/// * `matches` - Predicate on the panic message deciding whether to retry
+/// * `f` - The function to execute
+///
+/// # Panics
+/// Re-panics with the original payload on a non-matching panic, or when the
+/// last attempt panics.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(with_retry_on_panic(3, |msg| msg.contains("connection reset")))]
+/// fn fetch() -> Data {
+///     // ...
+/// }
+/// ```
+pub fn with_retry_on_panic<F, R, M>(attempts: u32, matches: M, f: F) -> R
+where
+    F: Fn() -> R,
+    M: Fn(&str) -> bool,
+{
+    let config = RetryConfig::new(attempts.max(1));
+    let mut delay = config.initial_delay;
+
+    for attempt in 1..=config.max_attempts {
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(&f)) {
+            Ok(result) => return result,
+            Err(payload) => {
+                let message = extract_panic_message(&payload);
+
+                if !matches(&message) {
+                    error!(
+                        attempt = %attempt,
+                        panic_message = %message,
+                        "❌ Non-retryable panic"
+                    );
+                    std::panic::resume_unwind(payload);
+                }
+
+                warn!(
+                    attempt = %attempt,
+                    max_attempts = %config.max_attempts,
+                    panic_message = %message,
+                    "❌ Attempt {} panicked",
+                    attempt
+                );
+
+                if attempt == config.max_attempts {
+                    error!(attempts = %config.max_attempts, "❌ All {} attempts failed", attempt);
+                    std::panic::resume_unwind(payload);
+                }
+
+                let actual_delay = add_jitter(delay);
+                info!(delay_ms = %actual_delay.as_millis(), "⏳ Waiting before retry");
+                thread::sleep(actual_delay);
+
+                delay = Duration::from_secs_f64(
+                    (delay.as_secs_f64() * config.backoff_multiplier)
+                        .min(config.max_delay.as_secs_f64()),
+                );
+            }
+        }
+    }
+
+    unreachable!()
+}
+
 /// Retries a Result-returning function.
 ///
 /// # Arguments
@@ -186,13 +260,52 @@ where
 
 /// Retries a Result-returning function with full configuration.
 pub fn with_retry_result_config<F, R, E>(config: &RetryConfig, f: F) -> Result<R, E>
+where
+    F: Fn() -> Result<R, E>,
+    E: std::fmt::Debug,
+{
+    retry_collecting_errors(config, f).map_err(|mut errors| errors.pop().unwrap())
+}
+
+/// Retries a Result-returning function, returning every attempt's error if all fail.
+///
+/// Retries like [`with_retry_result`], but on total failure the errors of all
+/// attempts are returned in the order they occurred, which shows how a flaky
+/// operation failed differently from one attempt to the next.
+///
+/// The error type changes to `Vec<E>`, which the body of a `#[decorate]`d
+/// function can't return, so this is called directly around the operation.
+///
+/// # Arguments
+/// * `attempts` - Maximum number of attempts; `0` is treated as `1`
+/// * `f` - The function to execute
+///
+/// # Returns
+/// `Ok(R)` on success, or `Err` with one error per attempt if all attempts fail
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let result: Result<(), Vec<SyncError>> = with_retry_all_errors(3, || sync_inventory());
+/// // Err([Timeout, ConnectionReset, Timeout]) after three failed attempts
+/// ```
+pub fn with_retry_all_errors<F, R, E>(attempts: u32, f: F) -> Result<R, Vec<E>>
+where
+    F: Fn() -> Result<R, E>,
+    E: std::fmt::Debug,
+{
+    let config = RetryConfig::new(attempts.max(1));
+    retry_collecting_errors(&config, f)
+}
+
+fn retry_collecting_errors<F, R, E>(config: &RetryConfig, f: F) -> Result<R, Vec<E>>
 where
     F: Fn() -> Result<R, E>,
     E: std::fmt::Debug,
 {
     let start = Instant::now();
     let mut delay = config.initial_delay;
-    let mut last_error = None;
+    let mut errors = Vec::new();
 
     for attempt in 1..=config.max_attempts {
         info!(
@@ -222,7 +335,7 @@ where
                     "❌ Attempt {} failed",
                     attempt
                 );
-                last_error = Some(e);
+                errors.push(e);
 
                 if attempt < config.max_attempts {
                     let actual_delay = if config.jitter {
@@ -250,7 +363,146 @@ where
         config.max_attempts
     );
 
-    Err(last_error.unwrap())
+    Err(errors)
+}
+
+/// Retries a Result-returning function, returning the last error instead of panicking.
+///
+/// Waits `100 * attempt` milliseconds after each failed attempt, without jitter.
+/// Unlike [`with_retry_result`], the schedule grows linearly.
+///
+/// # Arguments
+/// * `attempts` - Maximum number of attempts
+/// * `f` - The function to execute
+///
+/// # Returns
+/// `Ok(R)` on the first success, or the `Err` from the final attempt
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(try_retry(3))]
+/// fn connect() -> Result<Connection, io::Error> {
+///     // ...
+/// }
+/// ```
+pub fn try_retry<F, R, E>(attempts: u32, f: F) -> Result<R, E>
+where
+    F: Fn() -> Result<R, E>,
+    E: std::fmt::Debug,
+{
+    let attempts = attempts.max(1);
+    let mut attempt = 1;
+
+    loop {
+        match f() {
+            Ok(result) => return Ok(result),
+            Err(e) if attempt >= attempts => {
+                error!(attempts = %attempt, error = ?e, "❌ All {} attempts failed", attempt);
+                return Err(e);
+            }
+            Err(e) => {
+                let delay = Duration::from_millis(100 * attempt as u64);
+                warn!(
+                    attempt = %attempt,
+                    error = ?e,
+                    delay_ms = %delay.as_millis(),
+                    "❌ Attempt {} failed, retrying",
+                    attempt
+                );
+                thread::sleep(delay);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Retries a Result-returning function with capped exponential backoff and full jitter.
+///
+/// The delay before retry `n` is drawn uniformly from `0..min(cap, base * 2^(n-1))`,
+/// which spreads out retries from many callers. Returns the last error once all
+/// attempts fail, without panicking.
+///
+/// # Arguments
+/// * `attempts` - Maximum number of attempts
+/// * `base` - Delay ceiling before the first retry
+/// * `cap` - Upper bound for any delay
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(with_retry_robust(5, Duration::from_millis(100), Duration::from_secs(5)))]
+/// fn fetch_config() -> Result<Config, Error> {
+///     // ...
+/// }
+/// ```
+pub fn with_retry_robust<F, R, E>(
+    attempts: u32,
+    base: Duration,
+    cap: Duration,
+    f: F,
+) -> Result<R, E>
+where
+    F: Fn() -> Result<R, E>,
+    E: std::fmt::Debug,
+{
+    with_retry_robust_using(attempts, base, cap, thread::sleep, random_unit, f)
+}
+
+/// Like [`with_retry_robust`], with injectable sleeping and randomness.
+///
+/// `random` must return values in `0.0..1.0`; it scales each capped delay.
+pub fn with_retry_robust_using<F, R, E, S, J>(
+    attempts: u32,
+    base: Duration,
+    cap: Duration,
+    mut sleep: S,
+    mut random: J,
+    f: F,
+) -> Result<R, E>
+where
+    F: Fn() -> Result<R, E>,
+    E: std::fmt::Debug,
+    S: FnMut(Duration),
+    J: FnMut() -> f64,
+{
+    let mut ceiling = base.min(cap);
+
+    for attempt in 1..=attempts.max(1) {
+        match f() {
+            Ok(result) => return Ok(result),
+            Err(e) if attempt >= attempts => {
+                error!(attempts = %attempt, error = ?e, "❌ All {} attempts failed", attempt);
+                return Err(e);
+            }
+            Err(e) => {
+                let delay = ceiling.mul_f64(random().clamp(0.0, 1.0));
+                warn!(
+                    attempt = %attempt,
+                    error = ?e,
+                    delay_ms = %delay.as_millis(),
+                    "❌ Attempt {} failed, retrying",
+                    attempt
+                );
+                sleep(delay);
+                ceiling = ceiling.saturating_mul(2).min(cap);
+            }
+        }
+    }
+
+    unreachable!()
+}
+
+/// Returns a pseudo-random value in `0.0..1.0`, the randomness source for jitter in these examples.
+pub(crate) fn random_unit() -> f64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    // Each RandomState is freshly keyed, so hashing a constant still varies per call
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u8(0);
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
 }
 
 /// Adds random jitter to a duration (±25%).
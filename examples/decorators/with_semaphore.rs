@@ -0,0 +1,143 @@
+//! Semaphore decorators limiting how many calls run concurrently.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Condvar, LazyLock, Mutex, MutexGuard};
+use tracing::info;
+
+/// Permit accounting for a single key.
+#[derive(Debug, Default)]
+struct SemaphoreState {
+    in_use: usize,
+    next_ticket: u64,
+    queue: VecDeque<u64>,
+}
+
+#[derive(Debug, Default)]
+struct Semaphore {
+    state: Mutex<SemaphoreState>,
+    released: Condvar,
+}
+
+impl Semaphore {
+    fn lock(&self) -> MutexGuard<'_, SemaphoreState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn acquire(&self, max: usize) -> Permit<'_> {
+        let max = max.max(1);
+        let mut state = self.lock();
+        while state.in_use >= max {
+            state = self
+                .released
+                .wait(state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        state.in_use += 1;
+        Permit(self)
+    }
+
+    fn acquire_fair(&self, max: usize) -> Permit<'_> {
+        let max = max.max(1);
+        let mut state = self.lock();
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        state.queue.push_back(ticket);
+
+        while state.queue.front() != Some(&ticket) || state.in_use >= max {
+            state = self
+                .released
+                .wait(state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        state.queue.pop_front();
+        state.in_use += 1;
+        // The next ticket may be able to take a remaining permit
+        self.released.notify_all();
+        Permit(self)
+    }
+}
+
+/// Returns its permit when the call returns or unwinds.
+struct Permit<'a>(&'a Semaphore);
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        self.0.lock().in_use -= 1;
+        self.0.released.notify_all();
+    }
+}
+
+static SEMAPHORES: LazyLock<Mutex<HashMap<String, Arc<Semaphore>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn semaphore(key: &str) -> Arc<Semaphore> {
+    SEMAPHORES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .entry(key.to_string())
+        .or_default()
+        .clone()
+}
+
+/// Limits the number of concurrent executions sharing a key.
+///
+/// Calls beyond `max` block until a running call finishes. Permits go to
+/// whichever waiter wakes first, so under constant contention a caller can be
+/// overtaken repeatedly; use [`with_semaphore_fair`] when that matters.
+///
+/// # Arguments
+/// * `key` - Unique identifier for this semaphore
+/// * `max` - Maximum number of concurrent executions; `0` is treated as `1`
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(with_semaphore("db", 4))]
+/// fn run_query(sql: &str) -> Rows {
+///     // At most four queries run at once
+/// }
+/// ```
+pub fn with_semaphore<F, R>(key: &str, max: usize, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let semaphore = semaphore(key);
+    let _permit = semaphore.acquire(max);
+    info!(key = %key, "🚦 Acquired permit");
+
+    f()
+}
+
+/// Limits concurrent executions like [`with_semaphore`], granting permits in FIFO order.
+///
+/// Each caller takes a ticket on arrival and waits until it is at the head of
+/// the queue and a permit is free, so no caller is starved under contention.
+/// Calls made through [`with_semaphore`] with the same key do not queue and may
+/// still take a free permit first.
+///
+/// # Arguments
+/// * `key` - Unique identifier for this semaphore
+/// * `max` - Maximum number of concurrent executions; `0` is treated as `1`
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(with_semaphore_fair("exports", 2))]
+/// fn export_report(id: u64) -> Report {
+///     // Queued exports start in the order they were requested
+/// }
+/// ```
+pub fn with_semaphore_fair<F, R>(key: &str, max: usize, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let semaphore = semaphore(key);
+    let _permit = semaphore.acquire_fair(max);
+    info!(key = %key, "🎫 Acquired permit in turn");
+
+    f()
+}
@@ -1,6 +1,10 @@
 //! Timeout decorator for bounding execution time.
 
-use std::sync::mpsc;
+use super::safe_decorator::extract_panic_message;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, mpsc};
 use std::thread;
 use std::time::Duration;
 use tracing::{error, info};
@@ -12,6 +16,8 @@ pub enum TimeoutError<E> {
     Timeout { duration: Duration },
     /// The operation failed with an error
     Inner(E),
+    /// The operation panicked
+    Panicked { payload: String },
 }
 
 impl<E: std::fmt::Display> std::fmt::Display for TimeoutError<E> {
@@ -21,6 +27,7 @@ impl<E: std::fmt::Display> std::fmt::Display for TimeoutError<E> {
                 write!(f, "Operation timed out after {:?}", duration)
             }
             TimeoutError::Inner(e) => write!(f, "{}", e),
+            TimeoutError::Panicked { payload } => write!(f, "Operation panicked: {}", payload),
         }
     }
 }
@@ -41,7 +48,8 @@ impl<E: std::error::Error + 'static> std::error::Error for TimeoutError<E> {
 /// * `f` - The function to execute
 ///
 /// # Returns
-/// `Ok(R)` if completed within timeout, `Err(TimeoutError::Timeout)` otherwise
+/// `Ok(R)` if completed within timeout, `Err(TimeoutError::Timeout)` otherwise,
+/// or `Err(TimeoutError::Panicked)` if `f` panicked
 ///
 /// # Note
 /// This spawns a new thread for the operation. For async code, use async timeout utilities.
@@ -65,17 +73,22 @@ where
     info!(timeout_ms = %timeout_ms, "⏳ Starting operation with timeout");
 
     let handle = thread::spawn(move || {
-        let result = f();
-        let _ = tx.send(result);
+        let result = panic::catch_unwind(AssertUnwindSafe(f));
+        let _ = tx.send(result.map_err(|payload| extract_panic_message(&payload)));
     });
 
     match rx.recv_timeout(timeout) {
-        Ok(result) => {
+        Ok(Ok(result)) => {
             info!("✅ Operation completed within timeout");
             // Wait for thread to finish (it should be done already)
             let _ = handle.join();
             Ok(result)
         }
+        Ok(Err(payload)) => {
+            error!(payload = %payload, "❌ Operation thread panicked");
+            let _ = handle.join();
+            Err(TimeoutError::Panicked { payload })
+        }
         Err(mpsc::RecvTimeoutError::Timeout) => {
             error!(
                 timeout_ms = %timeout_ms,
@@ -86,14 +99,297 @@ where
             Err(TimeoutError::Timeout { duration: timeout })
         }
         Err(mpsc::RecvTimeoutError::Disconnected) => {
-            error!("❌ Operation thread panicked");
-            Err(TimeoutError::Inner("Thread panicked".to_string()))
+            error!("❌ Operation thread exited without a result");
+            Err(TimeoutError::Panicked {
+                payload: "worker thread exited without a result".to_string(),
+            })
+        }
+    }
+}
+
+/// Executes a function with a timeout, signalling it to stop once the timeout fires.
+///
+/// `f` receives a cancellation flag that is set when the timeout expires, so
+/// long-running work can poll it and return early instead of running on in
+/// the background like with [`with_timeout`].
+///
+/// # Arguments
+/// * `timeout_ms` - Maximum execution time in milliseconds
+/// * `f` - The function to execute, given the cancellation flag
+///
+/// # Returns
+/// `Ok(R)` if completed within timeout, `Err(TimeoutError::Timeout)` otherwise,
+/// or `Err(TimeoutError::Panicked)` if `f` panicked
+///
+/// # Note
+/// Cancellation is cooperative: the flag is only a request, and a closure that
+/// never checks it keeps running exactly as with [`with_timeout`]. The caller
+/// does not wait for the closure to observe the flag.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// // The flag is passed to the body, so this is called directly rather than
+/// // through `#[decorate]`, whose closures take no arguments
+/// let indexed = with_timeout_cancellable(5000, move |cancelled| {
+///     let mut indexed = 0;
+///     for file in files {
+///         if cancelled.load(Ordering::Relaxed) {
+///             break;
+///         }
+///         index(file);
+///         indexed += 1;
+///     }
+///     indexed
+/// });
+/// ```
+pub fn with_timeout_cancellable<F, R>(timeout_ms: u64, f: F) -> Result<R, TimeoutError<String>>
+where
+    F: FnOnce(&AtomicBool) -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let timeout = Duration::from_millis(timeout_ms);
+    let (tx, rx) = mpsc::channel();
+    let cancelled = Arc::new(AtomicBool::new(false));
+
+    info!(timeout_ms = %timeout_ms, "⏳ Starting cancellable operation with timeout");
+
+    let flag = Arc::clone(&cancelled);
+    let handle = thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| f(&flag)));
+        let _ = tx.send(result.map_err(|payload| extract_panic_message(&payload)));
+    });
+
+    match rx.recv_timeout(timeout) {
+        Ok(Ok(result)) => {
+            info!("✅ Operation completed within timeout");
+            let _ = handle.join();
+            Ok(result)
+        }
+        Ok(Err(payload)) => {
+            error!(payload = %payload, "❌ Operation thread panicked");
+            let _ = handle.join();
+            Err(TimeoutError::Panicked { payload })
+        }
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            cancelled.store(true, Ordering::Relaxed);
+            error!(
+                timeout_ms = %timeout_ms,
+                "⏰ Operation timed out, cancellation requested"
+            );
+            Err(TimeoutError::Timeout { duration: timeout })
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            error!("❌ Operation thread exited without a result");
+            Err(TimeoutError::Panicked {
+                payload: "worker thread exited without a result".to_string(),
+            })
+        }
+    }
+}
+
+/// Awaits the future returned by `f` with a timeout, without spawning a thread.
+///
+/// The async counterpart of [`with_timeout`], built on `tokio::time::timeout`
+/// (requires the `tokio` feature and a tokio runtime). On timeout the future is
+/// dropped, so unlike the thread-based variants the work is actually cancelled
+/// at its next `.await` point.
+///
+/// # Arguments
+/// * `timeout_ms` - Maximum execution time in milliseconds
+/// * `f` - The function producing the future to await
+///
+/// # Returns
+/// `Ok(R)` if completed within timeout, `Err(TimeoutError::Timeout)` otherwise
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(with_timeout_async(5000))]
+/// async fn fetch_report(id: u64) -> Result<Report, TimeoutError<String>> {
+///     client.get_report(id).await
+/// }
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn with_timeout_async<F, Fut, R>(timeout_ms: u64, f: F) -> Result<R, TimeoutError<String>>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = R>,
+{
+    let timeout = Duration::from_millis(timeout_ms);
+
+    info!(timeout_ms = %timeout_ms, "⏳ Starting async operation with timeout");
+
+    match tokio::time::timeout(timeout, f()).await {
+        Ok(result) => {
+            info!("✅ Operation completed within timeout");
+            Ok(result)
+        }
+        Err(_) => {
+            error!(timeout_ms = %timeout_ms, "⏰ Operation timed out");
+            Err(TimeoutError::Timeout { duration: timeout })
         }
     }
 }
 
+/// Executes a function with a timeout, allowing it to borrow non-`'static` data.
+///
+/// The body runs on a scoped thread (`std::thread::scope`), so it may capture
+/// references to the caller's stack instead of cloning them.
+///
+/// # Arguments
+/// * `timeout_ms` - Maximum execution time in milliseconds
+/// * `f` - The function to execute
+///
+/// # Returns
+/// `Ok(R)` if completed within timeout, `Err(TimeoutError::Timeout)` otherwise,
+/// or `Err(TimeoutError::Panicked)` if `f` panicked
+///
+/// # Note
+/// The scope must join its thread before borrowed data can be released, so on
+/// timeout this still waits for the body to finish; it only reports the
+/// overrun. Use it to detect slow calls, not to bound the caller's wait.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(with_timeout_scoped(500))]
+/// fn checksum(data: &[u8]) -> Result<u32, TimeoutError<String>> {
+///     data.iter().map(|&b| b as u32).sum()
+/// }
+/// ```
+pub fn with_timeout_scoped<F, R>(timeout_ms: u64, f: F) -> Result<R, TimeoutError<String>>
+where
+    F: FnOnce() -> R + Send,
+    R: Send,
+{
+    let timeout = Duration::from_millis(timeout_ms);
+    let (tx, rx) = mpsc::channel();
+
+    info!(timeout_ms = %timeout_ms, "⏳ Starting scoped operation with timeout");
+
+    thread::scope(|scope| {
+        let handle = scope.spawn(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(f));
+            let _ = tx.send(result.map_err(|payload| extract_panic_message(&payload)));
+        });
+
+        let outcome = match rx.recv_timeout(timeout) {
+            Ok(Ok(result)) => {
+                info!("✅ Operation completed within timeout");
+                Ok(result)
+            }
+            Ok(Err(payload)) => {
+                error!(payload = %payload, "❌ Operation thread panicked");
+                Err(TimeoutError::Panicked { payload })
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                error!(
+                    timeout_ms = %timeout_ms,
+                    "⏰ Operation timed out, waiting for scoped thread to finish"
+                );
+                Err(TimeoutError::Timeout { duration: timeout })
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                error!("❌ Operation thread exited without a result");
+                Err(TimeoutError::Panicked {
+                    payload: "worker thread exited without a result".to_string(),
+                })
+            }
+        };
+
+        let _ = handle.join();
+        outcome
+    })
+}
+
+/// Timeout statistics recorded by [`with_timeout_metered`].
+#[derive(Debug, Clone, Default)]
+pub struct TimeoutStats {
+    /// Number of decorated calls
+    pub total: u64,
+    /// Number of calls that exceeded the timeout
+    pub timed_out: u64,
+    /// Number of calls that completed within the timeout
+    pub succeeded: u64,
+}
+
+impl TimeoutStats {
+    pub fn timeout_rate(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.timed_out as f64 / self.total as f64
+        }
+    }
+}
+
+static TIMEOUT_STATS: LazyLock<Mutex<HashMap<String, TimeoutStats>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Executes a function with a timeout, recording per-name timeout statistics.
+///
+/// Behaves like [`with_timeout`] and additionally counts how often the operation
+/// exceeds its budget, retrievable with [`get_timeout_stats`].
+///
+/// # Arguments
+/// * `name` - Name under which the statistics are recorded
+/// * `timeout_ms` - Maximum execution time in milliseconds
+/// * `f` - The function to execute
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[decorate(with_timeout_metered("fetch_report", 2000))]
+/// fn fetch_report() -> Report {
+///     // Timeout rate visible via get_timeout_stats("fetch_report")
+/// }
+/// ```
+pub fn with_timeout_metered<F, R>(
+    name: &str,
+    timeout_ms: u64,
+    f: F,
+) -> Result<R, TimeoutError<String>>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let result = with_timeout(timeout_ms, f);
+
+    let mut stats = TIMEOUT_STATS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let entry = stats.entry(name.to_string()).or_default();
+    entry.total += 1;
+    match result {
+        Ok(_) => entry.succeeded += 1,
+        Err(TimeoutError::Timeout { .. }) => entry.timed_out += 1,
+        Err(TimeoutError::Inner(_) | TimeoutError::Panicked { .. }) => {}
+    }
+
+    info!(
+        name = %name,
+        total = %entry.total,
+        timed_out = %entry.timed_out,
+        "📊 Timeout statistics recorded"
+    );
+
+    result
+}
+
+/// Gets the timeout statistics recorded for a name.
+pub fn get_timeout_stats(name: &str) -> Option<TimeoutStats> {
+    TIMEOUT_STATS
+        .lock()
+        .ok()
+        .and_then(|stats| stats.get(name).cloned())
+}
+
 /// Executes a fallible function with a timeout.
 ///
+/// A panic in `f` is caught on the worker thread and returned as
+/// `Err(TimeoutError::Panicked)` rather than unwinding the caller.
+///
 /// # Arguments
 /// * `timeout_ms` - Maximum execution time in milliseconds
 /// * `f` - The function to execute
@@ -109,30 +405,37 @@ where
     info!(timeout_ms = %timeout_ms, "⏳ Starting fallible operation with timeout");
 
     let handle = thread::spawn(move || {
-        let result = f();
-        let _ = tx.send(result);
+        let result = panic::catch_unwind(AssertUnwindSafe(f));
+        let _ = tx.send(result.map_err(|payload| extract_panic_message(&payload)));
     });
 
     match rx.recv_timeout(timeout) {
-        Ok(Ok(result)) => {
+        Ok(Ok(Ok(result))) => {
             info!("✅ Operation succeeded within timeout");
             let _ = handle.join();
             Ok(result)
         }
-        Ok(Err(e)) => {
+        Ok(Ok(Err(e))) => {
             info!("❌ Operation failed within timeout");
             let _ = handle.join();
             Err(TimeoutError::Inner(e))
         }
+        Ok(Err(payload)) => {
+            error!(payload = %payload, "❌ Operation thread panicked");
+            let _ = handle.join();
+            Err(TimeoutError::Panicked { payload })
+        }
         Err(mpsc::RecvTimeoutError::Timeout) => {
             error!(timeout_ms = %timeout_ms, "⏰ Operation timed out");
             Err(TimeoutError::Timeout { duration: timeout })
         }
         Err(mpsc::RecvTimeoutError::Disconnected) => {
-            error!("❌ Operation thread panicked");
-            // This is a bit awkward - we need an E but the thread panicked
-            // In practice, you'd want a more sophisticated error type
-            panic!("Operation thread panicked")
+            // Panics in `f` are caught above, so this only happens if the
+            // worker dies while converting the payload
+            error!("❌ Operation thread exited without a result");
+            Err(TimeoutError::Panicked {
+                payload: "worker thread exited without a result".to_string(),
+            })
         }
     }
 }
@@ -47,10 +47,13 @@ extern crate proc_macro;
 use either::Either;
 use proc_macro::TokenStream;
 use proc_macro2::Span;
-use quote::{quote, quote_spanned};
+use quote::{format_ident, quote, quote_spanned};
 use syn::{
-    Error, Expr, FnArg, Ident, ItemFn, Pat, Path, Result, Token, parse::Parse,
-    punctuated::Punctuated, spanned::Spanned,
+    Error, Expr, FnArg, GenericArgument, Ident, ItemFn, Pat, Path, PathArguments, Result,
+    ReturnType, Signature, Stmt, Token, Type,
+    parse::Parse,
+    punctuated::{Pair, Punctuated},
+    spanned::Spanned,
 };
 
 // ============================================================================
@@ -61,12 +64,64 @@ mod error_messages {
     pub const NO_DECORATORS: &str = "no decorator paths provided";
     pub const CONST_FN_NOT_SUPPORTED: &str = "cannot decorate const functions";
     pub const CONST_FN_HELP: &str = "remove the `const` keyword or use a regular function";
+    pub const TRAIT_METHOD_WITHOUT_BODY: &str = "cannot decorate a trait method without a body";
+    pub const TRAIT_METHOD_WITHOUT_BODY_HELP: &str =
+        "give the method a default body, or decorate it in each `impl` instead";
     pub const SELF_PATH_MUST_START_WITH_SELF: &str = "path must start with 'self'";
-    pub const SELF_PATH_EMPTY_SEGMENT: &str = "path contains empty segment";
-    pub const SELF_PATH_INVALID_SEGMENT: &str = "path segment must be a valid identifier";
+    pub const SELF_PATH_UNSUPPORTED: &str =
+        "self path must be a chain of field accesses, method calls, and indexing";
     pub const UNKNOWN_CONFIG_OPTION: &str = "unknown configuration option";
-    pub const UNKNOWN_CONFIG_HELP: &str =
-        "valid options are: pre, post, transform_params, transform_result";
+    pub const UNKNOWN_CONFIG_HELP: &str = "valid options are: pre, post, finally, transform_params, transform_params_method, transform_params_struct, transform_result, transform_result_try, on_some, on_none, catch, map_panic, instrument, trace_max_depth, around, max_duration, on_slow, spawn_blocking, keep_original, labeled_body, cost, pass_args, retry_on_err, mockable, when, register";
+    pub const OPTION_HOOKS_REQUIRE_OPTION: &str =
+        "`on_some` and `on_none` require a function returning `Option`";
+    pub const OPTION_HOOKS_HELP: &str = "change the return type to `Option<T>`";
+    pub const CATCH_REQUIRES_RESULT: &str =
+        "`catch = true` and `map_panic` require a function returning `Result`";
+    pub const CATCH_RESULT_HELP: &str = "change the return type to `Result<T, E>`";
+    pub const CATCH_ASYNC_NOT_SUPPORTED: &str = "`catch` is not supported on async functions";
+    pub const CATCH_ASYNC_HELP: &str =
+        "panics in a future surface when it is polled, not when the body is called";
+    pub const TRANSFORM_PARAMS_RECEIVER: &str = "`transform_params` does not transform `self`; only typed parameters are passed to the transform";
+    pub const TRANSFORM_PARAMS_RECEIVER_HELP: &str = "use `transform_params_method = \"self.<method>\"` to transform the parameters with access to `self`";
+    pub const TRACE_MAX_DEPTH_REQUIRES_INSTRUMENT: &str =
+        "`trace_max_depth` requires `instrument` in the same entry";
+    pub const TRACE_MAX_DEPTH_INSTRUMENT_HELP: &str =
+        "add `instrument = <span>` for the span whose nesting is limited";
+    pub const TRACE_MAX_DEPTH_ASYNC_NOT_SUPPORTED: &str =
+        "`trace_max_depth` is not supported on async functions";
+    pub const TRACE_MAX_DEPTH_ASYNC_HELP: &str =
+        "the depth is tracked per thread, which a future may leave at any `.await`";
+    pub const ON_SLOW_REQUIRES_MAX_DURATION: &str =
+        "`on_slow` requires `max_duration` in the same entry";
+    pub const ON_SLOW_MAX_DURATION_HELP: &str =
+        "add `max_duration = <Duration>` for the limit that triggers the handler";
+    pub const RETRY_ON_ERR_REQUIRES_RESULT: &str =
+        "`retry_on_err` requires a function returning `Result`";
+    pub const MOCKABLE_UNSUPPORTED: &str =
+        "`mockable` requires a free function without type or const generics";
+    pub const MOCKABLE_HELP: &str =
+        "the override is a plain `fn` pointer, which cannot name `self` or generic parameters";
+    pub const MOCKABLE_ASSOCIATED_FN: &str = "`mockable` cannot be used on an associated function";
+    pub const MOCKABLE_ASSOCIATED_FN_HELP: &str = "the generated `thread_local!` slot and `<name>_mock` setter cannot be placed in an `impl` block; move the function out of it";
+    pub const MOCKABLE_PATTERN_PARAM: &str =
+        "`mockable` requires every parameter to be a plain identifier";
+    pub const MOCKABLE_PATTERN_HELP: &str =
+        "bind the parameter to a name and destructure it in the body";
+    pub const SPAWN_BLOCKING_REQUIRES_ASYNC: &str = "`spawn_blocking` requires an async function";
+    pub const SPAWN_BLOCKING_ASYNC_HELP: &str =
+        "add `async` so callers can await the offloaded body";
+    pub const SPAWN_BLOCKING_REQUIRES_FEATURE: &str =
+        "the `spawn_blocking` option requires the `tokio` feature";
+    pub const SPAWN_BLOCKING_FEATURE_HELP: &str =
+        "enable it with `decorate_macro = { features = [\"tokio\"] }`";
+    pub const NAMED_ARG_AFTER_POSITIONAL: &str =
+        "named decorator arguments must come before positional ones";
+    pub const NAMED_ARG_AFTER_POSITIONAL_HELP: &str =
+        "names are only documentation, so remove the name or move the argument forward";
+    pub const REGISTER_REQUIRES_FEATURE: &str =
+        "the `register` option requires the `inventory` feature";
+    pub const REGISTER_FEATURE_HELP: &str =
+        "enable it with `decorate_macro = { features = [\"inventory\"] }`";
 }
 
 // ============================================================================
@@ -77,8 +132,31 @@ mod error_messages {
 struct DecoratorConfig {
     pre_code: Option<Expr>,
     post_code: Option<Expr>,
-    transform_params: Option<Path>,
-    transform_result: Option<Path>,
+    finally_code: Option<Expr>,
+    transform_params: Option<Either<Path, Expr>>,
+    transform_params_method: Option<Expr>,
+    transform_params_struct: Option<(Path, Path)>,
+    transform_result: Option<Either<Path, Expr>>,
+    transform_result_try: Option<Path>,
+    on_some: Option<Expr>,
+    on_none: Option<Expr>,
+    catch: bool,
+    catch_code: Option<Either<Path, Expr>>,
+    map_panic: Option<Either<Path, Expr>>,
+    instrument: Option<Expr>,
+    trace_max_depth: Option<Expr>,
+    around: Option<Either<Path, Expr>>,
+    max_duration: Option<Expr>,
+    on_slow: Option<Either<Path, Expr>>,
+    spawn_blocking: bool,
+    keep_original: bool,
+    labeled_body: bool,
+    cost: Option<Expr>,
+    pass_args: bool,
+    retry_on_err: Option<Expr>,
+    mockable: bool,
+    when_code: Option<Expr>,
+    register: Option<Path>,
 }
 
 impl DecoratorConfig {
@@ -86,8 +164,44 @@ impl DecoratorConfig {
     fn has_any(&self) -> bool {
         self.pre_code.is_some()
             || self.post_code.is_some()
+            || self.finally_code.is_some()
             || self.transform_params.is_some()
+            || self.transform_params_method.is_some()
+            || self.transform_params_struct.is_some()
             || self.transform_result.is_some()
+            || self.transform_result_try.is_some()
+            || self.has_option_hooks()
+            || self.has_catch()
+            || self.instrument.is_some()
+            || self.trace_max_depth.is_some()
+            || self.around.is_some()
+            || self.max_duration.is_some()
+            || self.on_slow.is_some()
+            || self.spawn_blocking
+            || self.keep_original
+            || self.labeled_body
+            || self.cost.is_some()
+            || self.pass_args
+            || self.retry_on_err.is_some()
+            || self.mockable
+            || self.when_code.is_some()
+            || self.register.is_some()
+    }
+
+    #[inline]
+    fn has_option_hooks(&self) -> bool {
+        self.on_some.is_some() || self.on_none.is_some()
+    }
+
+    #[inline]
+    fn has_catch(&self) -> bool {
+        self.catches_into_err() || self.catch_code.is_some()
+    }
+
+    /// Whether a caught panic becomes the `Err` of the function's `Result`.
+    #[inline]
+    fn catches_into_err(&self) -> bool {
+        self.catch || self.map_panic.is_some()
     }
 }
 
@@ -95,11 +209,30 @@ impl DecoratorConfig {
 // Decorator Call Parser
 // ============================================================================
 
+/// The decorator invoked by an entry, as written in the attribute.
+struct DecoratorTarget {
+    path: Either<Path, Expr>,
+    span: Span,
+    label: String,
+    /// Whether `path` is a string self-path, which must be called in place
+    /// rather than bound to a variable first
+    is_self_path: bool,
+}
+
+/// A named decorator argument whose name was stripped, like `attempts` in
+/// `with_retry(attempts = 3)`.
+struct NamedArgument {
+    name: Ident,
+    /// Zero-based position the value is passed at
+    position: usize,
+}
+
 struct DecoratorCall {
     config: Option<DecoratorConfig>,
-    path: Either<Path, Expr>,
-    path_span: Span,
+    /// `None` for a trailing configuration-only entry
+    target: Option<DecoratorTarget>,
     args: Option<Punctuated<Expr, Token![,]>>,
+    named_args: Vec<NamedArgument>,
 }
 
 impl Parse for DecoratorCall {
@@ -112,10 +245,62 @@ impl Parse for DecoratorCall {
             input.parse::<Token![=]>()?;
 
             match key.to_string().as_str() {
-                "pre" => config.pre_code = Some(input.parse()?),
-                "post" => config.post_code = Some(input.parse()?),
-                "transform_params" => config.transform_params = Some(input.parse()?),
-                "transform_result" => config.transform_result = Some(input.parse()?),
+                "pre" => config.pre_code = Some(parse_hook_code(input)?),
+                "post" => config.post_code = Some(parse_hook_code(input)?),
+                "finally" => config.finally_code = Some(parse_hook_code(input)?),
+                "transform_params" => config.transform_params = Some(parse_path_or_expr(input)?),
+                "transform_params_method" => {
+                    let path_str: syn::LitStr = input.parse()?;
+                    config.transform_params_method =
+                        Some(parse_self_path(&path_str.value(), path_str.span())?);
+                }
+                "transform_params_struct" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    let transform: Path = content.parse()?;
+                    content.parse::<Token![,]>()?;
+                    let struct_path: Path = content.parse()?;
+                    config.transform_params_struct = Some((transform, struct_path));
+                }
+                "transform_result" => config.transform_result = Some(parse_path_or_expr(input)?),
+                "transform_result_try" => config.transform_result_try = Some(input.parse()?),
+                "on_some" => config.on_some = Some(input.parse()?),
+                "on_none" => config.on_none = Some(input.parse()?),
+                "catch" if input.peek(syn::LitBool) => {
+                    config.catch = input.parse::<syn::LitBool>()?.value
+                }
+                "catch" => config.catch_code = Some(parse_path_or_expr(input)?),
+                "map_panic" => config.map_panic = Some(parse_path_or_expr(input)?),
+                "instrument" => config.instrument = Some(input.parse()?),
+                "trace_max_depth" => config.trace_max_depth = Some(input.parse()?),
+                "around" => config.around = Some(parse_path_or_expr(input)?),
+                "max_duration" => config.max_duration = Some(input.parse()?),
+                "on_slow" => config.on_slow = Some(parse_path_or_expr(input)?),
+                "spawn_blocking" if cfg!(feature = "tokio") => {
+                    config.spawn_blocking = input.parse::<syn::LitBool>()?.value
+                }
+                "spawn_blocking" => {
+                    return Err(create_error_with_help(
+                        key_span,
+                        error_messages::SPAWN_BLOCKING_REQUIRES_FEATURE,
+                        error_messages::SPAWN_BLOCKING_FEATURE_HELP,
+                    ));
+                }
+                "keep_original" => config.keep_original = input.parse::<syn::LitBool>()?.value,
+                "labeled_body" => config.labeled_body = input.parse::<syn::LitBool>()?.value,
+                "cost" => config.cost = Some(input.parse()?),
+                "pass_args" => config.pass_args = input.parse::<syn::LitBool>()?.value,
+                "retry_on_err" => config.retry_on_err = Some(input.parse()?),
+                "mockable" => config.mockable = input.parse::<syn::LitBool>()?.value,
+                "when" => config.when_code = Some(input.parse()?),
+                "register" if cfg!(feature = "inventory") => config.register = Some(input.parse()?),
+                "register" => {
+                    return Err(create_error_with_help(
+                        key_span,
+                        error_messages::REGISTER_REQUIRES_FEATURE,
+                        error_messages::REGISTER_FEATURE_HELP,
+                    ));
+                }
                 _ => {
                     return Err(create_error_with_help(
                         key_span,
@@ -130,32 +315,120 @@ impl Parse for DecoratorCall {
             }
         }
 
-        let (path, path_span) = if input.peek(syn::LitStr) {
+        if let Some(max_depth) = &config.trace_max_depth
+            && config.instrument.is_none()
+        {
+            return Err(create_error_with_help(
+                max_depth.span(),
+                error_messages::TRACE_MAX_DEPTH_REQUIRES_INSTRUMENT,
+                error_messages::TRACE_MAX_DEPTH_INSTRUMENT_HELP,
+            ));
+        }
+
+        if let Some(on_slow) = &config.on_slow
+            && config.max_duration.is_none()
+        {
+            let span = match on_slow {
+                Either::Left(path) => path.span(),
+                Either::Right(expr) => expr.span(),
+            };
+            return Err(create_error_with_help(
+                span,
+                error_messages::ON_SLOW_REQUIRES_MAX_DURATION,
+                error_messages::ON_SLOW_MAX_DURATION_HELP,
+            ));
+        }
+
+        // Options may stand alone as the last entry, e.g. `#[decorate(pre = setup())]`
+        if input.is_empty() && config.has_any() {
+            return Ok(DecoratorCall {
+                config: Some(config),
+                target: None,
+                args: None,
+                named_args: Vec::new(),
+            });
+        }
+
+        let target = if input.peek(syn::LitStr) {
             let path_str: syn::LitStr = input.parse()?;
             let span = path_str.span();
-            (
-                Either::Right(parse_self_path(&path_str.value(), span)?),
+            DecoratorTarget {
+                path: Either::Right(parse_self_path(&path_str.value(), span)?),
                 span,
-            )
+                label: path_str.value(),
+                is_self_path: true,
+            }
+        } else if input.peek(syn::token::Paren) {
+            // A parenthesized expression evaluates to the decorator, e.g. a
+            // factory call `(make_logger("ctx"))`, while `make_logger("ctx")`
+            // would pass `"ctx"` to `make_logger` along with the body
+            let content;
+            syn::parenthesized!(content in input);
+            let expr: Expr = content.parse()?;
+            DecoratorTarget {
+                span: expr.span(),
+                label: quote!(#expr).to_string().replace(' ', ""),
+                path: Either::Right(expr),
+                is_self_path: false,
+            }
+        } else if starts_method_chain(input) {
+            // A method call on a path evaluates to the decorator too, e.g. reading
+            // a thread-local with `CURRENT_DECORATOR.with(|d| d.clone())`
+            let expr: Expr = input.parse()?;
+            DecoratorTarget {
+                span: expr.span(),
+                label: quote!(#expr).to_string().replace(' ', ""),
+                path: Either::Right(expr),
+                is_self_path: false,
+            }
         } else {
             let path: Path = input.parse()?;
-            let span = path.span();
-            (Either::Left(path), span)
+            DecoratorTarget {
+                span: path.span(),
+                label: quote!(#path).to_string().replace(' ', ""),
+                path: Either::Left(path),
+                is_self_path: false,
+            }
         };
 
+        let mut named_args = Vec::new();
         let args = if input.peek(syn::token::Paren) {
             let content;
             syn::parenthesized!(content in input);
-            Some(Punctuated::parse_terminated(&content)?)
+            let args: Punctuated<Expr, Token![,]> = Punctuated::parse_terminated(&content)?;
+            let mut seen_positional = false;
+            let mut pairs = Vec::with_capacity(args.len());
+            for (position, pair) in args.into_pairs().enumerate() {
+                let (arg, comma) = pair.into_tuple();
+                let arg = match strip_argument_name(arg) {
+                    Either::Left((name, value)) => {
+                        if seen_positional {
+                            return Err(create_error_with_help(
+                                name.span(),
+                                error_messages::NAMED_ARG_AFTER_POSITIONAL,
+                                error_messages::NAMED_ARG_AFTER_POSITIONAL_HELP,
+                            ));
+                        }
+                        named_args.push(NamedArgument { name, position });
+                        value
+                    }
+                    Either::Right(arg) => {
+                        seen_positional = true;
+                        arg
+                    }
+                };
+                pairs.push(Pair::new(arg, comma));
+            }
+            Some(pairs.into_iter().collect())
         } else {
             None
         };
 
         Ok(DecoratorCall {
             config: if config.has_any() { Some(config) } else { None },
-            path,
-            path_span,
+            target: Some(target),
             args,
+            named_args,
         })
     }
 }
@@ -165,12 +438,30 @@ impl Parse for DecoratorCall {
 // ============================================================================
 
 struct DecoratorList {
+    /// A leading `allow_empty` flag, which turns an empty list into a no-op
+    allow_empty: Option<Ident>,
     decorators: Punctuated<DecoratorCall, Token![,]>,
 }
 
 impl Parse for DecoratorList {
     fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+        let fork = input.fork();
+        let is_flag = fork
+            .parse::<Ident>()
+            .is_ok_and(|ident| ident == "allow_empty")
+            && (fork.is_empty() || fork.peek(Token![,]));
+        let allow_empty = if is_flag {
+            let flag = input.parse::<Ident>()?;
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+            Some(flag)
+        } else {
+            None
+        };
+
         Ok(DecoratorList {
+            allow_empty,
             decorators: Punctuated::parse_terminated(input)?,
         })
     }
@@ -180,81 +471,427 @@ impl Parse for DecoratorList {
 // Helper Functions
 // ============================================================================
 
+/// Whether the input starts with a path followed by `.`, like `LOGGER.with(..)`.
+///
+/// Paths starting with `self` are excluded, since a method on `self` is named
+/// with a string self-path such as `"self.logger.log"`.
+fn starts_method_chain(input: syn::parse::ParseStream) -> bool {
+    let fork = input.fork();
+    fork.parse::<Path>().is_ok_and(|path| {
+        path.segments
+            .first()
+            .is_some_and(|segment| segment.ident != "self")
+    }) && fork.peek(Token![.])
+}
+
 fn create_error_with_help(span: Span, message: &str, help: &str) -> Error {
     let mut err = Error::new(span, message);
     err.combine(Error::new(span, format!("help: {}", help)));
     err
 }
 
-fn is_valid_identifier(s: &str) -> bool {
-    if s.is_empty() {
-        return false;
+/// Parses a string self-path such as `"self.logger"`, `"self.loggers.0.log"`, or
+/// `"self.inner.logger()"` into an expression.
+///
+/// The path must be a chain of field accesses, method calls, and indexing
+/// starting at `self`. All tokens take the literal's span, so diagnostics point
+/// at the string.
+fn parse_self_path(s: &str, span: Span) -> Result<Expr> {
+    let tokens: proc_macro2::TokenStream = s.parse().map_err(|e| {
+        Error::new(
+            span,
+            format!("{}: {}", error_messages::SELF_PATH_UNSUPPORTED, e),
+        )
+    })?;
+    let expr: Expr = syn::parse2(respan(tokens, span)).map_err(|e| {
+        Error::new(
+            span,
+            format!("{}: {}", error_messages::SELF_PATH_UNSUPPORTED, e),
+        )
+    })?;
+
+    validate_self_path(&expr, span)?;
+    Ok(expr)
+}
+
+fn validate_self_path(expr: &Expr, span: Span) -> Result<()> {
+    match expr {
+        Expr::Path(expr_path) if expr_path.path.is_ident("self") => Ok(()),
+        Expr::Path(_) => Err(Error::new(
+            span,
+            error_messages::SELF_PATH_MUST_START_WITH_SELF,
+        )),
+        Expr::Field(field) => validate_self_path(&field.base, span),
+        Expr::MethodCall(call) => validate_self_path(&call.receiver, span),
+        Expr::Index(index) => validate_self_path(&index.expr, span),
+        Expr::Paren(paren) => validate_self_path(&paren.expr, span),
+        _ => Err(Error::new(span, error_messages::SELF_PATH_UNSUPPORTED)),
     }
-    let mut chars = s.chars();
-    match chars.next() {
-        Some(c) if c.is_alphabetic() || c == '_' => {}
-        _ => return false,
+}
+
+/// Finds the first occurrence of `ident` in `tokens`, including inside groups.
+fn find_ident(tokens: proc_macro2::TokenStream, ident: &str) -> Option<Span> {
+    tokens.into_iter().find_map(|token| match token {
+        proc_macro2::TokenTree::Ident(found) if found == ident => Some(found.span()),
+        proc_macro2::TokenTree::Group(group) => find_ident(group.stream(), ident),
+        _ => None,
+    })
+}
+
+/// Sets the span of every token, including those nested in groups.
+fn respan(tokens: proc_macro2::TokenStream, span: Span) -> proc_macro2::TokenStream {
+    tokens
+        .into_iter()
+        .map(|mut token| {
+            if let proc_macro2::TokenTree::Group(group) = &token {
+                let mut respanned =
+                    proc_macro2::Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                token = respanned.into();
+            } else {
+                token.set_span(span);
+            }
+            token
+        })
+        .collect()
+}
+
+/// Checks whether a return type is written as `name<..>` (by any path), e.g. `Option`.
+fn returns_type_named(output: &ReturnType, name: &str) -> bool {
+    match output {
+        ReturnType::Type(_, ty) => match &**ty {
+            Type::Path(type_path) => type_path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == name),
+            _ => false,
+        },
+        ReturnType::Default => false,
     }
-    chars.all(|c| c.is_alphanumeric() || c == '_')
 }
 
-fn parse_self_path(s: &str, span: Span) -> Result<Expr> {
-    let segments: Vec<&str> = s.split('.').collect();
+/// Checks that the signature supports the configuration options in use.
+fn validate_signature(
+    decorators: &Punctuated<DecoratorCall, Token![,]>,
+    sig: &Signature,
+) -> Result<()> {
+    let uses = |check: fn(&DecoratorConfig) -> bool| {
+        decorators
+            .iter()
+            .any(|decorator| decorator.config.as_ref().is_some_and(check))
+    };
+    let return_span = match &sig.output {
+        ReturnType::Type(_, ty) => ty.span(),
+        ReturnType::Default => sig.ident.span(),
+    };
 
-    if segments.is_empty() || segments[0] != "self" {
-        return Err(Error::new(
-            span,
-            error_messages::SELF_PATH_MUST_START_WITH_SELF,
+    if uses(DecoratorConfig::has_option_hooks) && !returns_type_named(&sig.output, "Option") {
+        return Err(create_error_with_help(
+            return_span,
+            error_messages::OPTION_HOOKS_REQUIRE_OPTION,
+            error_messages::OPTION_HOOKS_HELP,
         ));
     }
 
-    for (i, segment) in segments.iter().enumerate() {
-        if segment.is_empty() {
-            return Err(Error::new(span, error_messages::SELF_PATH_EMPTY_SEGMENT));
+    if uses(DecoratorConfig::has_catch)
+        && let Some(async_token) = &sig.asyncness
+    {
+        return Err(create_error_with_help(
+            async_token.span(),
+            error_messages::CATCH_ASYNC_NOT_SUPPORTED,
+            error_messages::CATCH_ASYNC_HELP,
+        ));
+    }
+
+    if uses(|config| config.trace_max_depth.is_some())
+        && let Some(async_token) = &sig.asyncness
+    {
+        return Err(create_error_with_help(
+            async_token.span(),
+            error_messages::TRACE_MAX_DEPTH_ASYNC_NOT_SUPPORTED,
+            error_messages::TRACE_MAX_DEPTH_ASYNC_HELP,
+        ));
+    }
+
+    if uses(|config| config.transform_params.is_some())
+        && let Some(FnArg::Receiver(receiver)) = sig.inputs.first()
+    {
+        return Err(create_error_with_help(
+            receiver.span(),
+            error_messages::TRANSFORM_PARAMS_RECEIVER,
+            error_messages::TRANSFORM_PARAMS_RECEIVER_HELP,
+        ));
+    }
+
+    if uses(|config| config.retry_on_err.is_some()) && !returns_type_named(&sig.output, "Result") {
+        return Err(create_error_with_help(
+            return_span,
+            error_messages::RETRY_ON_ERR_REQUIRES_RESULT,
+            error_messages::CATCH_RESULT_HELP,
+        ));
+    }
+
+    if uses(|config| config.mockable) {
+        let generic = sig
+            .generics
+            .params
+            .iter()
+            .find(|param| !matches!(param, syn::GenericParam::Lifetime(_)));
+        if let Some(span) = generic
+            .map(Spanned::span)
+            .or_else(|| match sig.inputs.first() {
+                Some(FnArg::Receiver(receiver)) => Some(receiver.span()),
+                _ => None,
+            })
+        {
+            return Err(create_error_with_help(
+                span,
+                error_messages::MOCKABLE_UNSUPPORTED,
+                error_messages::MOCKABLE_HELP,
+            ));
         }
-        if i > 0 && !is_valid_identifier(segment) {
-            return Err(Error::new(
+
+        // The hook's items cannot live in an `impl` block. A proc macro cannot see
+        // its surroundings, so only signatures naming `Self` are caught here
+        if let Some(span) = find_ident(quote!(#sig), "Self") {
+            return Err(create_error_with_help(
                 span,
-                format!(
-                    "{}: '{}'",
-                    error_messages::SELF_PATH_INVALID_SEGMENT,
-                    segment
-                ),
+                error_messages::MOCKABLE_ASSOCIATED_FN,
+                error_messages::MOCKABLE_ASSOCIATED_FN_HELP,
+            ));
+        }
+
+        let pattern = sig.inputs.iter().find_map(|arg| match arg {
+            FnArg::Typed(pat_type) if !matches!(*pat_type.pat, Pat::Ident(_)) => {
+                Some(pat_type.pat.span())
+            }
+            _ => None,
+        });
+        if let Some(span) = pattern {
+            return Err(create_error_with_help(
+                span,
+                error_messages::MOCKABLE_PATTERN_PARAM,
+                error_messages::MOCKABLE_PATTERN_HELP,
             ));
         }
     }
 
-    let self_ident = Ident::new("self", span);
-    let mut expr: Expr = syn::parse_quote_spanned!(span=> #self_ident);
+    if uses(|config| config.spawn_blocking) && sig.asyncness.is_none() {
+        return Err(create_error_with_help(
+            sig.fn_token.span(),
+            error_messages::SPAWN_BLOCKING_REQUIRES_ASYNC,
+            error_messages::SPAWN_BLOCKING_ASYNC_HELP,
+        ));
+    }
 
-    for segment in segments.iter().skip(1) {
-        let field_ident = Ident::new(segment, span);
-        expr = syn::parse_quote_spanned!(span=> (#expr).#field_ident);
+    if uses(DecoratorConfig::catches_into_err) && !returns_type_named(&sig.output, "Result") {
+        return Err(create_error_with_help(
+            return_span,
+            error_messages::CATCH_REQUIRES_RESULT,
+            error_messages::CATCH_RESULT_HELP,
+        ));
     }
 
-    Ok(expr)
+    Ok(())
+}
+
+/// Returns the return type if it is a boxed trait object (`Box<dyn ..>` or
+/// `Pin<Box<dyn ..>>`), which needs an explicit coercion site.
+fn boxed_trait_object_return(output: &ReturnType) -> Option<&Type> {
+    fn last_generic_type<'a>(ty: &'a Type, ident: &str) -> Option<&'a Type> {
+        let Type::Path(type_path) = ty else {
+            return None;
+        };
+        let segment = type_path.path.segments.last()?;
+        if segment.ident != ident {
+            return None;
+        }
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+        args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+    }
+
+    let ReturnType::Type(_, ty) = output else {
+        return None;
+    };
+    let boxed = last_generic_type(ty, "Pin").unwrap_or(ty);
+    match last_generic_type(boxed, "Box")? {
+        Type::TraitObject(_) => Some(ty),
+        _ => None,
+    }
+}
+
+/// Whether the function returns `impl Future`, so its body evaluates to a future
+/// even though the signature is not `async`.
+fn returns_impl_future(output: &ReturnType) -> bool {
+    let ReturnType::Type(_, ty) = output else {
+        return false;
+    };
+    let Type::ImplTrait(impl_trait) = &**ty else {
+        return false;
+    };
+    impl_trait.bounds.iter().any(|bound| match bound {
+        syn::TypeParamBound::Trait(bound) => bound
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Future"),
+        _ => false,
+    })
+}
+
+/// Parses hook code: a single expression, or a brace block of statements.
+///
+/// A leading block is parsed on its own rather than as the start of a larger
+/// expression, and stored as an `Expr::Block` so hooks are spliced uniformly.
+fn parse_hook_code(input: syn::parse::ParseStream) -> Result<Expr> {
+    if input.peek(syn::token::Brace) {
+        Ok(Expr::Block(syn::ExprBlock {
+            attrs: Vec::new(),
+            label: None,
+            block: input.parse()?,
+        }))
+    } else {
+        input.parse()
+    }
+}
+
+/// Parses a function path, or any other expression such as a closure.
+///
+/// Plain paths stay paths so they keep their own spans in diagnostics.
+fn parse_path_or_expr(input: syn::parse::ParseStream) -> Result<Either<Path, Expr>> {
+    match input.parse()? {
+        Expr::Path(expr_path) if expr_path.qself.is_none() && expr_path.attrs.is_empty() => {
+            Ok(Either::Left(expr_path.path))
+        }
+        expr => Ok(Either::Right(expr)),
+    }
+}
+
+/// Splits a named decorator argument (`attempts = 3`) into its name and value,
+/// or returns a positional argument unchanged.
+///
+/// Rust functions have no named arguments, so names are purely documentation:
+/// arguments are still passed positionally in the order they are written, and
+/// each stripped name is reported with [`generate_named_argument_warning`].
+fn strip_argument_name(arg: Expr) -> Either<(Ident, Expr), Expr> {
+    match arg {
+        Expr::Assign(assign) => match &*assign.left {
+            Expr::Path(p) if p.qself.is_none() && p.attrs.is_empty() => match p.path.get_ident() {
+                Some(name) => Either::Left((name.clone(), *assign.right)),
+                None => Either::Right(Expr::Assign(assign)),
+            },
+            _ => Either::Right(Expr::Assign(assign)),
+        },
+        other => Either::Right(other),
+    }
+}
+
+/// Generates a statement that triggers a `deprecated` warning at `span`.
+///
+/// Proc macros cannot emit warnings on stable, but using a `#[deprecated]`
+/// item can; the warning shows `note` and can be silenced with
+/// `#[allow(deprecated)]` like any other.
+fn generate_warning(note: &str, span: Span) -> proc_macro2::TokenStream {
+    let marker = Ident::new("decorate_warning", span);
+    quote_spanned! {span=>
+        {
+            #[deprecated(note = #note)]
+            #[allow(non_camel_case_types)]
+            struct #marker;
+            let _ = #marker;
+        }
+    }
+}
+
+/// Leaves a function unchanged for an empty `allow_empty` list, apart from a
+/// warning that no decorators are applied.
+fn generate_empty_list_warning(flag: &Ident, item: TokenStream) -> TokenStream {
+    // Required trait methods have no body to carry the warning
+    let Ok(mut input_fn) = syn::parse::<ItemFn>(item.clone()) else {
+        return item;
+    };
+
+    let note = format!(
+        "`allow_empty` with no decorators leaves `{}` unchanged",
+        input_fn.sig.ident
+    );
+    let warning = generate_warning(&note, flag.span());
+    input_fn
+        .block
+        .stmts
+        .insert(0, Stmt::Expr(syn::parse_quote!(#warning), None));
+    quote!(#input_fn).into()
+}
+
+/// Warns that a named decorator argument is passed positionally, since the name
+/// is not checked against the decorator's parameters.
+fn generate_named_argument_warning(
+    named: &NamedArgument,
+    decorator: &str,
+) -> proc_macro2::TokenStream {
+    let note = format!(
+        "named argument `{}` is passed as positional argument {} of `{}`; \
+         names are not checked against the decorator's parameters",
+        named.name,
+        named.position + 1,
+        decorator
+    );
+    generate_warning(&note, named.name.span())
 }
 
 fn extract_param_names(inputs: &Punctuated<FnArg, Token![,]>) -> Vec<&Ident> {
-    inputs
-        .iter()
-        .filter_map(|arg| match arg {
-            FnArg::Typed(pat_type) => {
-                if let Pat::Ident(pat_ident) = &*pat_type.pat {
-                    Some(&pat_ident.ident)
-                } else {
-                    None
-                }
+    let mut names = Vec::new();
+    for arg in inputs {
+        if let FnArg::Typed(pat_type) = arg {
+            collect_pattern_bindings(&pat_type.pat, &mut names);
+        }
+    }
+    names
+}
+
+/// Collects the identifiers bound by a parameter pattern, in order, so
+/// destructured parameters like `[a, b]: [i32; 2]` contribute `a` and `b`.
+fn collect_pattern_bindings<'a>(pat: &'a Pat, names: &mut Vec<&'a Ident>) {
+    match pat {
+        Pat::Ident(pat_ident) => {
+            names.push(&pat_ident.ident);
+            if let Some((_, subpat)) = &pat_ident.subpat {
+                collect_pattern_bindings(subpat, names);
             }
-            FnArg::Receiver(_) => None,
-        })
-        .collect()
+        }
+        Pat::Slice(slice) => slice
+            .elems
+            .iter()
+            .for_each(|elem| collect_pattern_bindings(elem, names)),
+        Pat::Tuple(tuple) => tuple
+            .elems
+            .iter()
+            .for_each(|elem| collect_pattern_bindings(elem, names)),
+        Pat::TupleStruct(tuple_struct) => tuple_struct
+            .elems
+            .iter()
+            .for_each(|elem| collect_pattern_bindings(elem, names)),
+        Pat::Struct(pat_struct) => pat_struct
+            .fields
+            .iter()
+            .for_each(|field| collect_pattern_bindings(&field.pat, names)),
+        Pat::Reference(reference) => collect_pattern_bindings(&reference.pat, names),
+        Pat::Paren(paren) => collect_pattern_bindings(&paren.pat, names),
+        Pat::Type(pat_type) => collect_pattern_bindings(&pat_type.pat, names),
+        _ => {}
+    }
 }
 
 /// Generates a validated decorator call with clear error messages.
 ///
 /// This wraps the decorator invocation in a way that:
-/// 1. For regular paths: assigns the decorator to a local variable to isolate type errors
+/// 1. For regular paths and expressions: assigns the decorator to a local variable to isolate type errors
 /// 2. For self-paths (method calls): calls directly since methods can't be assigned to variables
 /// 3. Uses explicit closure typing to catch signature mismatches early
 /// 4. Preserves span information for accurate error locations
@@ -275,6 +912,7 @@ fn generate_validated_decorator_call(
     }
 
     // For regular paths, use intermediate variables for better error messages
+    let arity_check = generate_arity_check(args.as_ref().map_or(0, Punctuated::len), span);
     if let Some(args) = args {
         quote_spanned! {span=>
             {
@@ -282,6 +920,7 @@ fn generate_validated_decorator_call(
                 // Expected: fn(args..., impl FnOnce() -> R) -> R
                 let __decorate_fn = #decorator_expr;
                 let __decorate_closure = || #body;
+                #arity_check
                 __decorate_fn(#args, __decorate_closure)
             }
         }
@@ -292,12 +931,48 @@ fn generate_validated_decorator_call(
                 // Expected: fn(impl FnOnce() -> R) -> R
                 let __decorate_fn = #decorator_expr;
                 let __decorate_closure = || #body;
+                #arity_check
                 __decorate_fn(__decorate_closure)
             }
         }
     }
 }
 
+/// Generates a check that `__decorate_fn` takes `arg_count` arguments followed by
+/// `__decorate_closure`.
+///
+/// rustc's own error for a mismatched decorator only points at the extra closure
+/// argument. A proc macro cannot see the decorator's signature, so the hint comes
+/// from the name of the checking function, which rustc reports as the source of
+/// the failed bound alongside the original error.
+fn generate_arity_check(arg_count: usize, span: Span) -> proc_macro2::TokenStream {
+    let arg_types: Vec<_> = (0..arg_count)
+        .map(|i| format_ident!("__A{}", i, span = span))
+        .collect();
+
+    quote_spanned! {span=>
+        {
+            fn decorator_functions_must_accept_the_wrapped_closure_as_their_last_argument<
+                __F,
+                __C,
+                __R,
+                #(#arg_types),*
+            >(
+                _: &__F,
+                _: &__C,
+            ) where
+                __F: ::core::ops::FnOnce(#(#arg_types,)* __C) -> __R,
+            {
+            }
+
+            decorator_functions_must_accept_the_wrapped_closure_as_their_last_argument(
+                &__decorate_fn,
+                &__decorate_closure,
+            );
+        }
+    }
+}
+
 /// Generates a direct decorator call without intermediate variable assignment.
 /// Used for self-path decorators (method references) which can't be assigned to variables.
 fn generate_direct_decorator_call(
@@ -320,33 +995,58 @@ fn generate_direct_decorator_call(
 fn generate_decorated_body(
     decorators: &Punctuated<DecoratorCall, Token![,]>,
     original_body: &syn::Block,
-    fn_inputs: &Punctuated<FnArg, Token![,]>,
-    is_async: bool,
+    sig: &Signature,
 ) -> proc_macro2::TokenStream {
+    let is_async = sig.asyncness.is_some();
+
     // For async functions, we wrap the body in an async block so .await is valid
     // The outermost decorator receives `|| async { body }` and must .await it
     let mut decorated_body = if is_async {
         quote! { async #original_body }
+    } else if let Some(boxed_ty) = boxed_trait_object_return(&sig.output) {
+        // Inside the decorator closure the body is no longer a coercion site, so
+        // `Box::pin(async { .. })` would not unsize to the declared `dyn` type
+        quote! {
+            {
+                let __decorate_output: #boxed_ty = #original_body;
+                __decorate_output
+            }
+        }
     } else {
         quote! { #original_body }
     };
 
     for decorator in decorators.iter().rev() {
         if let Some(config) = &decorator.config {
-            decorated_body = apply_config_transformations(config, decorated_body, fn_inputs);
+            decorated_body = apply_config_transformations(config, decorated_body, sig);
         }
 
-        let (decorator_expr, is_self_path) = match &decorator.path {
-            Either::Left(path) => (quote!(#path), false),
-            Either::Right(expr) => (quote!(#expr), true),
+        let Some(target) = &decorator.target else {
+            continue;
+        };
+
+        let decorator_expr = match &target.path {
+            Either::Left(path) => quote!(#path),
+            Either::Right(expr) => quote!(#expr),
+        };
+
+        // With `pass_args`, the parameters follow the decorator's own arguments
+        let args = match &decorator.config {
+            Some(config) if config.pass_args => {
+                let param_names = extract_param_names(&sig.inputs);
+                let mut args = decorator.args.clone().unwrap_or_default();
+                args.push(syn::parse_quote!((#(#param_names),*)));
+                Some(args)
+            }
+            _ => decorator.args.clone(),
         };
 
         decorated_body = generate_validated_decorator_call(
             &decorator_expr,
-            &decorator.args,
+            &args,
             decorated_body,
-            is_self_path,
-            decorator.path_span,
+            target.is_self_path,
+            target.span,
         );
     }
 
@@ -358,17 +1058,310 @@ fn generate_decorated_body(
     decorated_body
 }
 
+/// Wraps `block` in a block labeled `'body`, so the user's code can
+/// `break 'body value;` out of it.
+///
+/// The label has a call-site span, which lets the body name it; bodies that
+/// never break don't warn about the unused label.
+fn labeled_block(block: &syn::Block) -> syn::Block {
+    let label = syn::Lifetime::new("'body", Span::call_site());
+    syn::parse_quote! {
+        {
+            #[allow(unused_labels)]
+            let __decorate_labeled = #label: #block;
+            __decorate_labeled
+        }
+    }
+}
+
+/// Generates `<name>_cost()`, a `const fn` returning the configured cost so
+/// tooling can look up how expensive a decorated function is declared to be.
+///
+/// Only `cfg` attributes are carried over, so the accessor exists exactly when
+/// the function does.
+fn generate_cost_accessor(input_fn: &ItemFn, cost: &Expr) -> proc_macro2::TokenStream {
+    let name = format_ident!("{}_cost", input_fn.sig.ident);
+    let cfgs = input_fn
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"));
+    let doc = format!(" Declared cost of [`{}`].", input_fn.sig.ident);
+    let vis = &input_fn.vis;
+
+    quote_spanned! {cost.span()=>
+        #[doc = #doc]
+        #(#cfgs)*
+        #[allow(dead_code)]
+        #vis const fn #name() -> u64 {
+            #cost
+        }
+    }
+}
+
+/// Generates the function unchanged under `<name>_undecorated`, for testing the
+/// body without its decorators.
+///
+/// Attributes other than doc comments are kept, so `cfg` and lint settings
+/// apply to both versions.
+fn generate_undecorated_sibling(input_fn: &ItemFn) -> proc_macro2::TokenStream {
+    let mut sig = input_fn.sig.clone();
+    sig.ident = format_ident!("{}_undecorated", sig.ident);
+    let attrs = input_fn
+        .attrs
+        .iter()
+        .filter(|attr| !attr.path().is_ident("doc"));
+    let doc = format!(" Undecorated version of [`{}`].", input_fn.sig.ident);
+    let vis = &input_fn.vis;
+    let body = &input_fn.block;
+
+    quote! {
+        #[doc = #doc]
+        #(#attrs)*
+        #[allow(dead_code)]
+        #vis #sig #body
+    }
+}
+
+/// Generates the test-only override for a `mockable` function: a thread-local
+/// `fn` pointer, a `<name>_mock` setter, and the check that runs the override
+/// in place of the body.
+///
+/// Returns the items to emit next to the function and the check to prepend to
+/// its body. Lifetime parameters become higher-ranked on the `fn` pointer.
+fn generate_mock_hook(
+    sig: &Signature,
+    vis: &syn::Visibility,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let name = &sig.ident;
+    let slot = format_ident!("__DECORATE_MOCK_{}", name.to_string().to_uppercase());
+    let setter = format_ident!("{}_mock", name);
+    let param_names = extract_param_names(&sig.inputs);
+    let param_types = sig.inputs.iter().filter_map(|arg| match arg {
+        FnArg::Typed(pat_type) => Some(&pat_type.ty),
+        FnArg::Receiver(_) => None,
+    });
+    let lifetimes = sig.generics.lifetimes();
+    let output = &sig.output;
+    let fn_type = quote! { for<#(#lifetimes),*> fn(#(#param_types),*) #output };
+    let doc = format!(
+        " Overrides [`{name}`] on the current thread in tests; `None` restores the real body."
+    );
+
+    let items = quote! {
+        #[cfg(test)]
+        ::std::thread_local! {
+            #[allow(non_upper_case_globals)]
+            static #slot: ::std::cell::Cell<::core::option::Option<#fn_type>> =
+                const { ::std::cell::Cell::new(::core::option::Option::None) };
+        }
+
+        #[doc = #doc]
+        #[cfg(test)]
+        #[allow(dead_code)]
+        #vis fn #setter(mock: ::core::option::Option<#fn_type>) {
+            #slot.with(|slot| slot.set(mock));
+        }
+    };
+    let check = quote! {
+        #[cfg(test)]
+        if let ::core::option::Option::Some(__decorate_mock) = #slot.with(::std::cell::Cell::get) {
+            return __decorate_mock(#(#param_names),*);
+        }
+    };
+
+    (items, check)
+}
+
+/// Generates an `inventory::submit!` entry describing the decorated function.
+///
+/// The registry type is user-defined, since a proc-macro crate cannot export it.
+fn generate_registration(
+    registry: &Path,
+    fn_name: &Ident,
+    decorators: &Punctuated<DecoratorCall, Token![,]>,
+) -> proc_macro2::TokenStream {
+    let name = fn_name.to_string();
+    let labels = decorators
+        .iter()
+        .filter_map(|decorator| Some(&decorator.target.as_ref()?.label));
+
+    quote! {
+        ::inventory::submit! {
+            #registry {
+                name: #name,
+                decorators: &[#(#labels),*],
+            }
+        }
+    }
+}
+
+/// Generates the `transform_params` call through a local generic function whose
+/// signature fixes the expected arity.
+///
+/// Calling the transform directly reports a mismatch as a confusing
+/// tuple-destructuring error; going through the helper's `FnOnce` bound reports
+/// a single error at the transform's path instead.
+fn generate_transform_params_call(
+    transform: &Either<Path, Expr>,
+    param_names: &[&Ident],
+) -> proc_macro2::TokenStream {
+    let (transform, span) = match transform {
+        Either::Left(path) => (quote!(#path), path.span()),
+        Either::Right(expr) => (quote!(#expr), expr.span()),
+    };
+    let inputs: Vec<_> = (0..param_names.len())
+        .map(|i| format_ident!("__A{}", i))
+        .collect();
+    let outputs: Vec<_> = (0..param_names.len())
+        .map(|i| format_ident!("__B{}", i))
+        .collect();
+    // A single parameter is returned as-is rather than as a 1-tuple
+    let output_ty = if outputs.len() == 1 {
+        quote! { #(#outputs)* }
+    } else {
+        quote! { (#(#outputs),*) }
+    };
+
+    let call = quote_spanned! {span=>
+        __decorate_transform_params(#transform, #(#param_names),*)
+    };
+
+    quote! {
+        {
+            #[allow(clippy::too_many_arguments)]
+            fn __decorate_transform_params<__F, #(#inputs,)* #(#outputs),*>(
+                __transform: __F,
+                #(#param_names: #inputs),*
+            ) -> #output_ty
+            where
+                __F: FnOnce(#(#inputs),*) -> #output_ty,
+            {
+                __transform(#(#param_names),*)
+            }
+
+            #call
+        }
+    }
+}
+
+/// Generates a call of a function path or closure with a single argument.
+fn generate_single_arg_call(
+    callable: &Either<Path, Expr>,
+    arg: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match callable {
+        Either::Left(path) => quote! { #path(#arg) },
+        // Passing the closure through a generic `FnOnce` bound lets its
+        // parameter type be inferred, which a direct `(|r| ..)(x)` call can't do
+        Either::Right(closure) => quote! {
+            {
+                fn __decorate_apply<__F, __A, __R>(callable: __F, value: __A) -> __R
+                where
+                    __F: FnOnce(__A) -> __R,
+                {
+                    callable(value)
+                }
+
+                __decorate_apply(#closure, #arg)
+            }
+        },
+    }
+}
+
 fn apply_config_transformations(
     config: &DecoratorConfig,
     mut body: proc_macro2::TokenStream,
-    fn_inputs: &Punctuated<FnArg, Token![,]>,
+    sig: &Signature,
 ) -> proc_macro2::TokenStream {
+    let fn_inputs = &sig.inputs;
+    let is_async = sig.asyncness.is_some();
+
+    if config.spawn_blocking {
+        // The body's future is driven to completion on a blocking-pool thread; a
+        // panic there is resumed in the caller
+        body = quote! {
+            async {
+                let __decorate_handle = ::tokio::runtime::Handle::current();
+                match ::tokio::task::spawn_blocking(move || __decorate_handle.block_on(#body)).await {
+                    ::core::result::Result::Ok(__decorate_result) => __decorate_result,
+                    ::core::result::Result::Err(__decorate_error) => {
+                        ::std::panic::resume_unwind(__decorate_error.into_panic())
+                    }
+                }
+            }
+        };
+    }
+
+    if let Some(retries) = &config.retry_on_err {
+        let retry_loop = |attempt: proc_macro2::TokenStream| {
+            quote! {
+                let __decorate_retries: u32 = #retries;
+                let mut __decorate_attempt: u32 = 0;
+                loop {
+                    #attempt
+                    match __decorate_result {
+                        ::core::result::Result::Err(_) if __decorate_attempt < __decorate_retries => {
+                            __decorate_attempt += 1;
+                        }
+                        __decorate_result => break __decorate_result,
+                    }
+                }
+            }
+        };
+        body = if is_async {
+            let retry_loop = retry_loop(quote! { let __decorate_result = #body.await; });
+            quote! { async { #retry_loop } }
+        } else {
+            // The closure scopes `?` to one attempt. Passing it through a helper
+            // bounded on `Result` lets `?` resolve while the error type is still
+            // inferred from whatever consumes the result
+            let retry_loop = retry_loop(quote! {
+                let __decorate_result = __decorate_try_attempt(|| #body);
+            });
+            quote! {
+                {
+                    fn __decorate_try_attempt<T, E>(
+                        f: impl FnOnce() -> ::core::result::Result<T, E>,
+                    ) -> ::core::result::Result<T, E> {
+                        f()
+                    }
+                    #retry_loop
+                }
+            }
+        };
+    }
+
     if let Some(transform) = &config.transform_params {
+        let param_names = extract_param_names(fn_inputs);
+        if !param_names.is_empty() {
+            let call = generate_transform_params_call(transform, &param_names);
+            body = quote! {
+                {
+                    let (#(#param_names),*) = #call;
+                    #body
+                }
+            };
+        }
+    }
+
+    if let Some(method) = &config.transform_params_method {
         let param_names = extract_param_names(fn_inputs);
         if !param_names.is_empty() {
             body = quote! {
                 {
-                    let (#(#param_names),*) = #transform(#(#param_names),*);
+                    let (#(#param_names),*) = #method(#(#param_names),*);
+                    #body
+                }
+            };
+        }
+    }
+
+    if let Some((transform, struct_path)) = &config.transform_params_struct {
+        let param_names = extract_param_names(fn_inputs);
+        if !param_names.is_empty() {
+            body = quote! {
+                {
+                    let #struct_path { #(#param_names),* } = #transform(#(#param_names),*);
                     #body
                 }
             };
@@ -394,11 +1387,238 @@ fn apply_config_transformations(
         };
     }
 
+    if let Some(finally) = &config.finally_code {
+        // The guard runs `finally` when dropped, i.e. on return and while unwinding
+        let guard = quote! {
+            struct __DecorateFinally<__F: ::core::ops::FnOnce()>(::core::option::Option<__F>);
+
+            impl<__F: ::core::ops::FnOnce()> ::core::ops::Drop for __DecorateFinally<__F> {
+                fn drop(&mut self) {
+                    if let ::core::option::Option::Some(finally) = self.0.take() {
+                        finally();
+                    }
+                }
+            }
+
+            let __decorate_finally = __DecorateFinally(::core::option::Option::Some(|| {
+                #finally;
+            }));
+        };
+        // In async functions the guard must live until the body's future resolves
+        body = if is_async {
+            quote! {
+                async {
+                    #guard
+                    #body.await
+                }
+            }
+        } else {
+            quote! {
+                {
+                    #guard
+                    #body
+                }
+            }
+        };
+    }
+
+    if config.has_option_hooks() {
+        let on_some = config.on_some.iter();
+        let on_none = config.on_none.iter();
+        let hooks = quote! {
+            match &__decorate_result {
+                ::core::option::Option::Some(_) => {
+                    #(#on_some;)*
+                }
+                ::core::option::Option::None => {
+                    #(#on_none;)*
+                }
+            }
+        };
+        // In async functions the body is a future, so the hooks run once it resolves
+        body = if is_async {
+            quote! {
+                async {
+                    let __decorate_result = #body.await;
+                    #hooks
+                    __decorate_result
+                }
+            }
+        } else {
+            quote! {
+                {
+                    let __decorate_result = #body;
+                    #hooks
+                    __decorate_result
+                }
+            }
+        };
+    }
+
     if let Some(transform) = &config.transform_result {
+        let call = generate_single_arg_call(transform, quote!(__decorate_result));
+        // When the body is a future, the transform maps its output instead. The
+        // future returned as `impl Future` must own its captures, hence `move`
+        body = if is_async {
+            quote! {
+                async {
+                    let __decorate_result = #body.await;
+                    #call
+                }
+            }
+        } else if returns_impl_future(&sig.output) {
+            quote! {
+                {
+                    let __decorate_future = #body;
+                    async move {
+                        let __decorate_result = __decorate_future.await;
+                        #call
+                    }
+                }
+            }
+        } else {
+            quote! {
+                {
+                    let __decorate_result = #body;
+                    #call
+                }
+            }
+        };
+    }
+
+    if let Some(transform) = &config.transform_result_try {
+        // The immediately-invoked closure scopes `?` and `return` to the body,
+        // so early errors still flow through the transform. An async body already
+        // scopes them to its own block, so only its output needs mapping
+        body = if is_async {
+            quote! {
+                async {
+                    let __decorate_result = #body.await;
+                    #transform(__decorate_result)
+                }
+            }
+        } else if returns_impl_future(&sig.output) {
+            quote! {
+                {
+                    let __decorate_future = #body;
+                    async move {
+                        let __decorate_result = __decorate_future.await;
+                        #transform(__decorate_result)
+                    }
+                }
+            }
+        } else {
+            quote! {
+                {
+                    let __decorate_result = (|| #body)();
+                    #transform(__decorate_result)
+                }
+            }
+        };
+    }
+
+    if config.has_catch() {
+        let on_panic = match (&config.catch_code, &config.map_panic) {
+            (Some(handler), _) => generate_single_arg_call(handler, quote!(__decorate_payload)),
+            (None, Some(map_panic)) => {
+                let error = generate_single_arg_call(map_panic, quote!(__decorate_payload));
+                quote! { ::core::result::Result::Err(#error) }
+            }
+            (None, None) => quote! {
+                ::core::result::Result::Err(::core::convert::From::from(__decorate_payload))
+            },
+        };
+        body = quote! {
+            match ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| #body)) {
+                ::core::result::Result::Ok(__decorate_result) => __decorate_result,
+                ::core::result::Result::Err(__decorate_payload) => #on_panic,
+            }
+        };
+    }
+
+    if let Some(max_duration) = &config.max_duration {
+        let fn_name = sig.ident.to_string();
+        // A soft limit: exceeding it is reported, but the result is still returned
+        let on_slow = match &config.on_slow {
+            Some(handler) => generate_single_arg_call(handler, quote!(__decorate_elapsed)),
+            None => quote! {
+                ::tracing::warn!(
+                    function = #fn_name,
+                    elapsed_ms = __decorate_elapsed.as_millis() as u64,
+                    max_duration_ms = __decorate_max_duration.as_millis() as u64,
+                    "max_duration exceeded"
+                )
+            },
+        };
+        let timed = |run: proc_macro2::TokenStream| {
+            quote! {
+                let __decorate_start = ::std::time::Instant::now();
+                let __decorate_result = #run;
+                let __decorate_elapsed = __decorate_start.elapsed();
+                let __decorate_max_duration: ::core::time::Duration = #max_duration;
+                if __decorate_elapsed > __decorate_max_duration {
+                    #on_slow;
+                }
+                __decorate_result
+            }
+        };
+        body = if is_async {
+            let timed = timed(quote! { #body.await });
+            quote! { async { #timed } }
+        } else {
+            let timed = timed(body);
+            quote! { { #timed } }
+        };
+    }
+
+    if let Some(span) = &config.instrument {
+        // An entered span guard would not follow an async body across `.await`
+        // points, so its future is instrumented instead
+        body = if is_async {
+            quote! { ::tracing::Instrument::instrument(#body, #span) }
+        } else if let Some(max_depth) = &config.trace_max_depth {
+            // Recursive calls nested deeper than the limit run without a span;
+            // the counter is local to this function and restored on unwind
+            quote! {
+                {
+                    ::std::thread_local! {
+                        static __DECORATE_TRACE_DEPTH: ::core::cell::Cell<usize> =
+                            const { ::core::cell::Cell::new(0) };
+                    }
+
+                    struct __DecorateDepthGuard;
+
+                    impl ::core::ops::Drop for __DecorateDepthGuard {
+                        fn drop(&mut self) {
+                            __DECORATE_TRACE_DEPTH.with(|depth| depth.set(depth.get() - 1));
+                        }
+                    }
+
+                    let __decorate_depth = __DECORATE_TRACE_DEPTH.with(|depth| {
+                        depth.set(depth.get() + 1);
+                        depth.get()
+                    });
+                    let __decorate_depth_guard = __DecorateDepthGuard;
+                    let __decorate_body = || #body;
+                    if __decorate_depth <= #max_depth {
+                        #span.in_scope(__decorate_body)
+                    } else {
+                        __decorate_body()
+                    }
+                }
+            }
+        } else {
+            quote! { #span.in_scope(|| #body) }
+        };
+    }
+
+    if let Some(around) = &config.around {
+        // Binding the closure first lets an `around` closure infer its parameter type
+        let call = generate_single_arg_call(around, quote!(__decorate_body));
         body = quote! {
             {
-                let __decorate_result = #body;
-                #transform(__decorate_result)
+                let __decorate_body = || #body;
+                #call
             }
         };
     }
@@ -436,12 +1656,134 @@ fn apply_config_transformations(
 /// }
 /// ```
 ///
+/// Arguments may be named for readability, as in `with_retry(attempts = 3)`.
+/// The name is stripped and the value is passed positionally, so named
+/// arguments must still appear in the decorator's parameter order and come
+/// before any positional ones. Since names are not checked against the
+/// decorator's parameters, each one triggers a `deprecated` warning naming the
+/// position it is passed at; `#[allow(deprecated)]` on the function silences it.
+/// To pass an assignment expression as an argument, wrap it in parentheses.
+///
+/// ## Decorator Expressions
+/// A parenthesized expression is evaluated to obtain the decorator, so a
+/// factory returning one can be used as `#[decorate((make_logger("ctx")))]`.
+/// Without the parentheses, `make_logger("ctx")` would instead be called with
+/// `"ctx"` and the closure:
+/// ```rust,ignore
+/// fn make_logger<F, R>(ctx: &'static str) -> impl FnOnce(F) -> R
+/// where
+///     F: FnOnce() -> R,
+/// {
+///     move |f| {
+///         println!("[{ctx}] calling");
+///         f()
+///     }
+/// }
+/// ```
+///
+/// A method call on a path needs no parentheses. Such expressions are evaluated
+/// in the function body on every call, so a per-thread decorator can be read
+/// from a `thread_local`, as in
+/// `#[decorate(CURRENT_LOGGER.with(|logger| logger.borrow().decorator()))]`.
+///
+/// ## Empty Decorator Lists
+/// `#[decorate()]` is an error. Macros that generate the list, and may leave it
+/// empty, can start it with the `allow_empty` flag: `#[decorate(allow_empty)]`
+/// leaves the function unchanged, while `#[decorate(allow_empty, log)]` is the
+/// same as `#[decorate(log)]`. An empty list still raises a `deprecated` warning
+/// on the flag so it is not missed; add `#[allow(deprecated)]` to the function
+/// where it is expected.
+///
 /// # Configuration Options
 ///
-/// * `pre = <expr>` - Code to execute before the function body
-/// * `post = <expr>` - Code to execute after the function body
-/// * `transform_params = <path>` - Function to transform parameters
-/// * `transform_result = <path>` - Function to transform the result
+/// Options precede the decorator they configure, or may form the last entry on
+/// their own, as in `#[decorate(transform_params = clamp)]`.
+///
+/// * `pre = <expr-or-block>` - Code to execute before the function body
+/// * `post = <expr-or-block>` - Code to execute after the function body
+/// * `finally = <expr-or-block>` - Code to execute after the function body, even when it
+///   panics. It runs from a drop guard, so anything it uses stays borrowed for
+///   the whole body
+/// * `transform_params = <path-or-closure>` - Function to transform parameters.
+///   Not supported on methods with a `self` receiver, which is never transformed;
+///   use `transform_params_method` there
+/// * `transform_params_method = "self.<method>"` - Like `transform_params`, but the
+///   transform is a method on the receiver, so it can use `self` state
+/// * `transform_params_struct = (<path>, <StructType>)` - Function returning a struct
+///   whose fields are rebound to the parameters of the same name
+/// * `transform_result = <path-or-closure>` - Function to transform the result;
+///   for `async fn` and functions returning `impl Future`, it transforms the
+///   future's output
+/// * `transform_result_try = <path>` - Like `transform_result`, but `?` in the body
+///   propagates into the transform's input error type (via `From`) instead of
+///   returning early, so the transform sees every error; for `async fn` and
+///   functions returning `impl Future`, it transforms the future's output
+/// * `on_some = <expr>` - Code to execute when the body returns `Some`
+/// * `on_none = <expr>` - Code to execute when the body returns `None`; both
+///   option hooks require the function to return `Option`
+/// * `catch = true` - Converts a panic in the body into `Err`, using the error
+///   type's `From<Box<dyn Any + Send>>` impl; requires a non-async function
+///   returning `Result`
+/// * `catch = <path-or-closure>` - Handles a panic in the body by calling the
+///   handler with the boxed payload; its result is returned in place of the
+///   body's. Not supported on async functions
+/// * `map_panic = <expr>` - Like `catch = true`, but converts the panic payload
+///   with the given function or closure instead of `From`
+/// * `instrument = <span>` - Runs the rest of the entry inside a `tracing::Span`.
+///   Async bodies are instrumented with `tracing::Instrument`, so the span stays
+///   attached across `.await` points. The calling crate must depend on `tracing`
+/// * `trace_max_depth = <expr>` - With `instrument`, creates the span only for
+///   the outermost `<expr>` levels of recursion; deeper calls run without one.
+///   Not supported on async functions
+/// * `around = <path-or-closure>` - Wrapper called with the rest of the entry's
+///   options and the body as a closure, like a decorator; it is applied outermost
+/// * `max_duration = <Duration>` - Soft time limit for the rest of the entry: a
+///   slower call logs a `tracing` warning, which requires the calling crate to
+///   depend on `tracing`, and its result is still returned
+/// * `on_slow = <path-or-closure>` - With `max_duration`, called with the elapsed
+///   `Duration` instead of logging the warning
+/// * `spawn_blocking = true` - Runs the body on tokio's blocking thread pool
+///   (requires the `tokio` feature and an async function). Parameters are moved
+///   into the blocking task, so they must be `Send + 'static`
+/// * `cost = <expr>` - Declares the function's cost, returned by a generated
+///   `const fn <name>_cost() -> u64` so linters and profilers can flag hot paths
+/// * `keep_original = true` - Also emits the undecorated function as
+///   `<name>_undecorated`, with the same signature, for testing the raw body
+/// * `labeled_body = true` - Wraps the function body in a block labeled `'body`,
+///   so it can exit early with `break 'body value;`. Only the body is exited:
+///   `post`, `transform_result` and the decorators still see the value. The
+///   label cannot be reached from closures or async blocks inside the body
+/// * `pass_args = true` - Also passes the function's parameters to the decorator,
+///   as an argument between its own arguments and the closure. Several parameters
+///   form a tuple, a single one is passed as is. They are passed by value, so
+///   parameters the body also uses must be `Copy`
+/// * `retry_on_err = <n>` - Reruns the body up to `n` more times while it returns
+///   `Err`, returning the first `Ok` or the last `Err`. Panics are not caught.
+///   Requires a function returning `Result`. Every attempt reruns the body, so
+///   the body cannot move a parameter out: parameters it consumes must be
+///   `Copy`, or be borrowed or cloned instead
+/// * `mockable = true` - In `cfg(test)` builds, generates `<name>_mock(Option<fn>)`
+///   to override the function on the current thread; while set, the override is
+///   called instead of the decorated body. Requires a free function without type
+///   or const generics whose parameters are plain identifiers. Associated
+///   functions are rejected when their signature names `Self`; others fail to
+///   compile at the generated items, since an `impl` block cannot hold them
+/// * `when = <expr>` - Runtime gate for the whole attribute, whichever entry it
+///   appears in: the decorated body runs only when the `bool` expression is
+///   true, and the undecorated body runs otherwise
+/// * `register = <Type>` - Submits an `inventory` entry for the function (requires
+///   the `inventory` feature). `Type` is defined by the caller, collected with
+///   `inventory::collect!`, and must have `name: &'static str` and
+///   `decorators: &'static [&'static str]` fields. Only free functions can be
+///   registered, since `inventory::submit!` cannot appear inside an `impl` block.
+///
+/// Anywhere in the attribute, `__fn_name` is a `&str` holding the decorated
+/// function's name, so hooks and decorator arguments can use it without
+/// hardcoding, as in `#[decorate(trace_calls_named(__fn_name))]`.
+///
+/// Panic handling (`catch` or `map_panic`) wraps the other options of the same
+/// entry: panics in `pre`, the body, or `post` are caught, and `post` is skipped
+/// when the body panics. `finally` still runs before the panic is handled.
 ///
 /// # Examples
 ///
@@ -597,7 +1939,13 @@ fn apply_config_transformations(
 /// ```
 #[proc_macro_attribute]
 pub fn decorate(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let uses_fn_name = find_ident(attr.clone().into(), "__fn_name").is_some();
     let decorator_list = match syn::parse::<DecoratorList>(attr) {
+        // Generated code may produce an empty list; the function is left as is
+        Ok(DecoratorList {
+            allow_empty: Some(flag),
+            decorators,
+        }) if decorators.is_empty() => return generate_empty_list_warning(&flag, item),
         Ok(list) if list.decorators.is_empty() => {
             return Error::new(Span::call_site(), error_messages::NO_DECORATORS)
                 .to_compile_error()
@@ -607,9 +1955,21 @@ pub fn decorate(attr: TokenStream, item: TokenStream) -> TokenStream {
         Err(e) => return e.to_compile_error().into(),
     };
 
-    let input_fn = match syn::parse::<ItemFn>(item) {
+    // Trait default methods parse as `ItemFn`; only required methods lack a body
+    let input_fn = match syn::parse::<ItemFn>(item.clone()) {
         Ok(f) => f,
-        Err(e) => return e.to_compile_error().into(),
+        Err(e) => {
+            return match syn::parse::<syn::TraitItemFn>(item) {
+                Ok(method) if method.default.is_none() => create_error_with_help(
+                    method.sig.span(),
+                    error_messages::TRAIT_METHOD_WITHOUT_BODY,
+                    error_messages::TRAIT_METHOD_WITHOUT_BODY_HELP,
+                ),
+                _ => e,
+            }
+            .to_compile_error()
+            .into();
+        }
     };
 
     if let Some(const_token) = &input_fn.sig.constness {
@@ -622,14 +1982,87 @@ pub fn decorate(attr: TokenStream, item: TokenStream) -> TokenStream {
         .into();
     }
 
+    if let Err(e) = validate_signature(&decorator_list.decorators, &input_fn.sig) {
+        return e.to_compile_error().into();
+    }
+
+    let mut input_fn = input_fn;
+    if decorator_list
+        .decorators
+        .iter()
+        .any(|decorator| decorator.config.as_ref().is_some_and(|c| c.labeled_body))
+    {
+        *input_fn.block = labeled_block(&input_fn.block);
+    }
+
     let vis = &input_fn.vis;
     let sig = &input_fn.sig;
     let body = &input_fn.block;
     let attrs = &input_fn.attrs;
+    let fn_name = sig.ident.to_string();
 
-    let is_async = sig.asyncness.is_some();
-    let decorated_body =
-        generate_decorated_body(&decorator_list.decorators, body, &sig.inputs, is_async);
+    let mut decorated_body = generate_decorated_body(&decorator_list.decorators, body, sig);
+
+    // A `when` gate falls back to the plain body, bypassing every decorator
+    if let Some(when) = decorator_list
+        .decorators
+        .iter()
+        .find_map(|decorator| decorator.config.as_ref()?.when_code.as_ref())
+    {
+        decorated_body = quote! {
+            if #when {
+                #decorated_body
+            } else #body
+        };
+    }
+
+    let registration = decorator_list
+        .decorators
+        .iter()
+        .find_map(|decorator| decorator.config.as_ref()?.register.as_ref())
+        .map(|registry| generate_registration(registry, &sig.ident, &decorator_list.decorators));
+
+    let original = decorator_list
+        .decorators
+        .iter()
+        .any(|decorator| decorator.config.as_ref().is_some_and(|c| c.keep_original))
+        .then(|| generate_undecorated_sibling(&input_fn));
+
+    let cost = decorator_list
+        .decorators
+        .iter()
+        .find_map(|decorator| decorator.config.as_ref()?.cost.as_ref())
+        .map(|cost| generate_cost_accessor(&input_fn, cost));
+
+    let named_argument_warnings: Vec<_> = decorator_list
+        .decorators
+        .iter()
+        .flat_map(|decorator| {
+            let label = decorator
+                .target
+                .as_ref()
+                .map_or("", |target| target.label.as_str());
+            decorator
+                .named_args
+                .iter()
+                .map(move |named| generate_named_argument_warning(named, label))
+        })
+        .collect();
+
+    // Only emitted when the attribute refers to it, so other bodies are untouched
+    let fn_name_const = uses_fn_name.then(|| {
+        quote! {
+            #[allow(non_upper_case_globals)]
+            const __fn_name: &str = #fn_name;
+        }
+    });
+
+    let (mock_items, mock_check) = decorator_list
+        .decorators
+        .iter()
+        .any(|decorator| decorator.config.as_ref().is_some_and(|c| c.mockable))
+        .then(|| generate_mock_hook(sig, vis))
+        .unzip();
 
     // Generate the output - same for sync and async functions
     // For async functions, the body can contain .await expressions
@@ -637,8 +2070,19 @@ pub fn decorate(attr: TokenStream, item: TokenStream) -> TokenStream {
     let output = quote_spanned! {sig.span()=>
         #(#attrs)*
         #vis #sig {
+            #fn_name_const
+            #(#named_argument_warnings)*
+            #mock_check
             #decorated_body
         }
+
+        #original
+
+        #cost
+
+        #mock_items
+
+        #registration
     };
 
     output.into()
@@ -98,3 +98,143 @@ fn test_mut_method_decoration() {
     assert_eq!(test.increment(), 1);
     assert_eq!(test.increment(), 2);
 }
+
+#[test]
+fn test_mockable_override_replaces_body() {
+    #[decorate(mockable = true, test_decorator)]
+    fn fetch_price(symbol: &str) -> u32 {
+        panic!("no network access for {symbol}")
+    }
+
+    fn price_of_pair(a: &str, b: &str) -> u32 {
+        fetch_price(a) + fetch_price(b)
+    }
+
+    fetch_price_mock(Some(|symbol| symbol.len() as u32));
+    assert_eq!(price_of_pair("ABC", "DEFG"), 7);
+
+    fetch_price_mock(None);
+    assert!(std::panic::catch_unwind(|| fetch_price("ABC")).is_err());
+}
+
+#[tokio::test]
+async fn test_instrument_span_survives_await() {
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[decorate(instrument = tracing::info_span!("fetch", id))]
+    async fn fetch(id: u32) -> u32 {
+        tokio::task::yield_now().await;
+        tracing::info!("after await");
+        id * 2
+    }
+
+    let capture = Capture::default();
+    let writer = capture.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    assert_eq!(fetch(7).await, 14);
+
+    let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+    assert!(
+        output.contains("fetch{id=7}: decorator_tests: after await"),
+        "{output}"
+    );
+}
+
+#[test]
+fn test_trace_max_depth_limits_recursive_spans() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+
+    struct SpanCounter(Arc<AtomicUsize>);
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for SpanCounter {
+        fn on_new_span(
+            &self,
+            _: &tracing::span::Attributes<'_>,
+            _: &tracing::span::Id,
+            _: Context<'_, S>,
+        ) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[decorate(instrument = tracing::info_span!("fib", n), trace_max_depth = 4, test_decorator)]
+    fn fib(n: u64) -> u64 {
+        if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+    }
+
+    let spans = Arc::new(AtomicUsize::new(0));
+    let subscriber = tracing_subscriber::registry().with(SpanCounter(spans.clone()));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    assert_eq!(fib(10), 55);
+    // One span per call in the top four levels of the call tree: 1 + 2 + 4 + 8
+    assert_eq!(spans.load(Ordering::SeqCst), 15);
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_spawn_blocking_runs_off_the_executor() {
+    use std::thread::{self, ThreadId};
+
+    #[decorate(spawn_blocking = true, test_decorator)]
+    async fn checksum(data: Vec<u8>) -> (ThreadId, u32) {
+        thread::sleep(std::time::Duration::from_millis(10));
+        (thread::current().id(), data.iter().map(|&b| b as u32).sum())
+    }
+
+    // The current-thread runtime polls this test on the test thread itself
+    let (body_thread, sum) = checksum(vec![1, 2, 3]).await;
+    assert_eq!(sum, 6);
+    assert_ne!(body_thread, thread::current().id());
+}
+
+#[cfg(feature = "inventory")]
+mod registration {
+    use super::test_decorator;
+    use decorate_macro::decorate;
+
+    pub struct DecoratedFn {
+        pub name: &'static str,
+        pub decorators: &'static [&'static str],
+    }
+
+    inventory::collect!(DecoratedFn);
+
+    #[decorate(register = DecoratedFn, test_decorator)]
+    fn registered_handler(x: i32) -> i32 {
+        x * 2
+    }
+
+    #[test]
+    fn test_register_submits_inventory_entry() {
+        assert_eq!(registered_handler(21), 42);
+
+        let entry = inventory::iter::<DecoratedFn>
+            .into_iter()
+            .find(|entry| entry.name == "registered_handler")
+            .expect("registered_handler should be registered");
+        assert_eq!(entry.decorators, ["test_decorator"]);
+    }
+}
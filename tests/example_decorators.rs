@@ -0,0 +1,2427 @@
+//! Behavioral tests for the decorators shipped in `examples/decorators`.
+//!
+//! Decorator state is process-global, so each test uses its own keys.
+
+#[allow(dead_code, unused_imports)]
+#[path = "../examples/decorators/mod.rs"]
+mod decorators;
+
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::subscriber::DefaultGuard;
+use tracing::{Level, Metadata};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+
+/// A span or event recorded by [`capture_tracing`].
+#[derive(Debug)]
+struct Captured {
+    is_span: bool,
+    level: Level,
+    name: &'static str,
+    is_root: bool,
+    fields: Vec<(&'static str, String)>,
+}
+
+impl Captured {
+    /// Gets a field formatted with `Debug`; an event's message is the `message` field.
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+type CapturedLog = Arc<Mutex<Vec<Captured>>>;
+
+/// Collects every field of a span or event.
+struct Fields(Vec<(&'static str, String)>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.push((field.name(), format!("{value:?}")));
+    }
+}
+
+/// Records spans and events at or above a level.
+struct Recorder {
+    level: Level,
+    log: CapturedLog,
+}
+
+impl Recorder {
+    fn record(
+        &self,
+        is_span: bool,
+        metadata: &Metadata<'static>,
+        is_root: bool,
+        visit: impl FnOnce(&mut Fields),
+    ) {
+        if *metadata.level() > self.level {
+            return;
+        }
+        let mut fields = Fields(Vec::new());
+        visit(&mut fields);
+        self.log.lock().unwrap().push(Captured {
+            is_span,
+            level: *metadata.level(),
+            name: metadata.name(),
+            is_root,
+            fields: fields.0,
+        });
+    }
+}
+
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for Recorder {
+    fn on_new_span(
+        &self,
+        attrs: &tracing::span::Attributes<'_>,
+        _: &tracing::span::Id,
+        _: Context<'_, S>,
+    ) {
+        self.record(true, attrs.metadata(), attrs.is_root(), |fields| {
+            attrs.record(fields)
+        });
+    }
+
+    fn on_event(&self, event: &tracing::Event<'_>, _: Context<'_, S>) {
+        self.record(false, event.metadata(), event.is_root(), |fields| {
+            event.record(fields)
+        });
+    }
+}
+
+/// Captures the spans and events at `level` or above on this thread until the guard is dropped.
+fn capture_tracing(level: Level) -> (DefaultGuard, CapturedLog) {
+    let log = CapturedLog::default();
+    let recorder = Recorder {
+        level,
+        log: log.clone(),
+    };
+    let guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder));
+    (guard, log)
+}
+
+/// Gets the messages of the captured events, in order.
+fn messages(log: &CapturedLog) -> Vec<String> {
+    log.lock()
+        .unwrap()
+        .iter()
+        .filter(|captured| !captured.is_span)
+        .filter_map(|captured| captured.field("message").map(str::to_string))
+        .collect()
+}
+
+/// Gets the value of field `name` on every captured span that has it, in order.
+fn span_fields(log: &CapturedLog, name: &str) -> Vec<String> {
+    log.lock()
+        .unwrap()
+        .iter()
+        .filter(|captured| captured.is_span)
+        .filter_map(|captured| captured.field(name).map(str::to_string))
+        .collect()
+}
+
+#[test]
+fn test_cache_reports_metrics() {
+    use decorators::Cache;
+    use metrics::{
+        Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    /// Records the name of every metric emitted.
+    #[derive(Default)]
+    struct NameRecorder(Arc<Mutex<Vec<String>>>);
+
+    impl NameRecorder {
+        fn record(&self, key: &Key) {
+            self.0.lock().unwrap().push(key.name().to_string());
+        }
+    }
+
+    impl Recorder for NameRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            self.record(key);
+            Counter::noop()
+        }
+
+        fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+            self.record(key);
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            self.record(key);
+            Histogram::noop()
+        }
+    }
+
+    let recorder = NameRecorder::default();
+    let cache = Cache::new(10);
+    let ttl = Duration::from_secs(60);
+
+    metrics::with_local_recorder(&recorder, || {
+        assert_eq!(
+            cache.get_or_compute("metrics:live", ttl, || Ok::<_, ()>(1)),
+            Ok(1)
+        );
+        assert_eq!(
+            cache.get_or_compute("metrics:live", ttl, || Ok::<_, ()>(2)),
+            Ok(1)
+        );
+    });
+
+    assert_eq!(
+        *recorder.0.lock().unwrap(),
+        ["cache_misses", "cache_size", "cache_hits"]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_trace_calls_profile_records_samples() {
+    use decorate_macro::decorate;
+    use decorators::{get_profile_folded, trace_calls_profile};
+
+    #[decorate(trace_calls_profile("busy_loop"))]
+    fn busy_loop() -> u64 {
+        let start = std::time::Instant::now();
+        let mut acc = 0u64;
+        while start.elapsed() < std::time::Duration::from_millis(300) {
+            acc = std::hint::black_box(acc.wrapping_mul(31).wrapping_add(7));
+        }
+        acc
+    }
+
+    busy_loop();
+
+    let folded = get_profile_folded("busy_loop").expect("profile recorded");
+    assert!(folded.contains("busy_loop"));
+    assert!(folded.lines().all(|line| {
+        line.rsplit_once(' ')
+            .is_some_and(|(_, n)| n.parse::<u64>().is_ok())
+    }));
+}
+
+/// Serializes tests using the global cache, since some shrink its capacity.
+static CACHE_CONFIG_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[test]
+fn test_pinned_cache_entry_survives_eviction() {
+    use decorators::{pin_cache_entry, set_cache_max_size, unpin_cache_entry, with_cache};
+    use std::time::Duration;
+
+    let _lock = CACHE_CONFIG_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let ttl = Duration::from_secs(60);
+
+    set_cache_max_size(2);
+    let config: Result<&str, ()> = with_cache("pin:config", ttl, || Ok("v1"));
+    assert_eq!(config, Ok("v1"));
+    assert!(pin_cache_entry("pin:config"));
+
+    for i in 0..5 {
+        let _: Result<i32, ()> = with_cache(&format!("pin:filler_{i}"), ttl, || Ok(i));
+    }
+
+    let config: Result<&str, ()> = with_cache("pin:config", ttl, || Ok("recomputed"));
+    assert_eq!(config, Ok("v1"));
+
+    assert!(unpin_cache_entry("pin:config"));
+    assert!(!pin_cache_entry("pin:missing"));
+    set_cache_max_size(1000);
+}
+
+#[test]
+fn test_retry_on_matching_panic() {
+    use decorators::with_retry_on_panic;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    let calls = AtomicU32::new(0);
+    let result = with_retry_on_panic(
+        3,
+        |msg| msg.contains("transient"),
+        || {
+            if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                panic!("transient failure");
+            }
+            42
+        },
+    );
+
+    assert_eq!(result, 42);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_retry_on_panic_runs_once_with_zero_attempts() {
+    use decorators::with_retry_on_panic;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    let calls = AtomicU32::new(0);
+    let result = with_retry_on_panic(0, |_| true, || calls.fetch_add(1, Ordering::SeqCst));
+
+    assert_eq!(result, 0);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_try_retry_succeeds_after_failures() {
+    use decorators::try_retry;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    let calls = AtomicU32::new(0);
+    let result: Result<i32, &str> = try_retry(3, || {
+        if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+            Err("transient")
+        } else {
+            Ok(42)
+        }
+    });
+
+    assert_eq!(result, Ok(42));
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_try_retry_returns_last_error() {
+    use decorators::try_retry;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    let calls = AtomicU32::new(0);
+    let result: Result<i32, u32> = try_retry(2, || Err(calls.fetch_add(1, Ordering::SeqCst)));
+
+    assert_eq!(result, Err(1));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_non_matching_panic_propagates_immediately() {
+    use decorators::with_retry_on_panic;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    let calls = AtomicU32::new(0);
+    let outcome = std::panic::catch_unwind(|| {
+        with_retry_on_panic(
+            3,
+            |msg| msg.contains("transient"),
+            || -> i32 {
+                calls.fetch_add(1, Ordering::SeqCst);
+                panic!("fatal failure");
+            },
+        )
+    });
+
+    let payload = outcome.expect_err("non-matching panic should propagate");
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"fatal failure"));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_backoff_metered_records_attempt_distribution() {
+    use decorators::{get_backoff_stats, with_backoff_metered};
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    let delay = Duration::from_millis(1);
+    for failures in [0u32, 2, 1] {
+        let calls = Cell::new(0u32);
+        let result: Result<u32, String> = with_backoff_metered("metered_ok", 5, delay, || {
+            calls.set(calls.get() + 1);
+            if calls.get() <= failures {
+                Err("transient".to_string())
+            } else {
+                Ok(calls.get())
+            }
+        });
+        assert_eq!(result, Ok(failures + 1));
+    }
+
+    let result: Result<(), &str> = with_backoff_metered("metered_fail", 3, delay, || Err("down"));
+    assert_eq!(result, Err("down"));
+
+    let stats = get_backoff_stats("metered_ok").expect("stats recorded");
+    assert_eq!(stats.total_calls, 3);
+    assert_eq!(stats.total_attempts, 1 + 3 + 2);
+    assert_eq!(stats.succeeded_on, vec![1, 3, 2]);
+
+    let stats = get_backoff_stats("metered_fail").expect("stats recorded");
+    assert_eq!(stats.total_calls, 1);
+    assert_eq!(stats.total_attempts, 3);
+    assert!(stats.succeeded_on.is_empty());
+    assert!(get_backoff_stats("metered_unknown").is_none());
+}
+
+#[test]
+fn test_refresh_ahead_serves_current_value_while_refreshing() {
+    use decorators::{set_refresh_ahead, with_cache_refreshable};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    let _lock = CACHE_CONFIG_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let ttl = Duration::from_millis(500);
+    let computations = Arc::new(AtomicU32::new(0));
+    let fetch = || {
+        let computations = Arc::clone(&computations);
+        move || -> Result<u32, ()> { Ok(computations.fetch_add(1, Ordering::SeqCst) + 1) }
+    };
+
+    set_refresh_ahead("refresh:rates", 0.2);
+    assert_eq!(with_cache_refreshable("refresh:rates", ttl, fetch()), Ok(1));
+
+    // Below the refresh threshold: plain hit, no refresh
+    assert_eq!(with_cache_refreshable("refresh:rates", ttl, fetch()), Ok(1));
+    assert_eq!(computations.load(Ordering::SeqCst), 1);
+
+    // Past the threshold: current value served, refresh triggered
+    thread::sleep(Duration::from_millis(150));
+    assert_eq!(with_cache_refreshable("refresh:rates", ttl, fetch()), Ok(1));
+
+    // The refreshed value replaces the entry once the background run lands
+    let mut latest = Ok(1);
+    for _ in 0..100 {
+        latest = with_cache_refreshable("refresh:rates", ttl, fetch());
+        if latest == Ok(2) {
+            break;
+        }
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert_eq!(latest, Ok(2));
+    assert_eq!(computations.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_timeout_metered_records_timeout_rate() {
+    use decorators::{get_timeout_stats, with_timeout_metered};
+    use std::thread;
+    use std::time::Duration;
+
+    for delay_ms in [0u64, 200, 0, 200] {
+        let result = with_timeout_metered("metered_timeout", 50, move || {
+            thread::sleep(Duration::from_millis(delay_ms));
+            delay_ms
+        });
+        assert_eq!(result.is_ok(), delay_ms == 0);
+    }
+
+    let stats = get_timeout_stats("metered_timeout").expect("stats recorded");
+    assert_eq!(stats.total, 4);
+    assert_eq!(stats.timed_out, 2);
+    assert_eq!(stats.succeeded, 2);
+    assert_eq!(stats.timeout_rate(), 0.5);
+    assert!(get_timeout_stats("metered_timeout_unknown").is_none());
+}
+
+/// Serializes tests using circuit breakers, since some reset all of them.
+static CIRCUIT_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[test]
+fn test_circuit_breaker_rate_opens_on_failure_rate() {
+    use decorators::{CircuitState, circuit_breaker_rate, get_circuit_state};
+    use std::time::Duration;
+
+    let _lock = CIRCUIT_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let window = Duration::from_secs(60);
+    let timeout = Duration::from_secs(60);
+    let call = |name: &str, ok: bool| -> Result<(), String> {
+        circuit_breaker_rate(name, 0.5, 4, window, timeout, || {
+            if ok {
+                Ok(())
+            } else {
+                Err("failed".to_string())
+            }
+        })
+    };
+
+    // Below the minimum sample count, even a 100% failure rate keeps it closed
+    for _ in 0..3 {
+        let _ = call("rate:min_samples", false);
+    }
+    assert_eq!(
+        get_circuit_state("rate:min_samples"),
+        Some(CircuitState::Closed)
+    );
+    let _ = call("rate:min_samples", false);
+    assert_eq!(
+        get_circuit_state("rate:min_samples"),
+        Some(CircuitState::Open)
+    );
+
+    // 1/4, 2/5 and 3/6 failures stay at or below the threshold
+    for ok in [true, true, false, true, false, false] {
+        let _ = call("rate:mixed", ok);
+        assert_eq!(get_circuit_state("rate:mixed"), Some(CircuitState::Closed));
+    }
+
+    // 4/7 exceeds it
+    let _ = call("rate:mixed", false);
+    assert_eq!(get_circuit_state("rate:mixed"), Some(CircuitState::Open));
+    assert_eq!(
+        call("rate:mixed", true),
+        Err("Circuit breaker 'rate:mixed' is open".to_string())
+    );
+}
+
+#[test]
+fn test_circuit_breaker_rate_based_opens_over_recent_calls() {
+    use decorators::{CircuitState, circuit_breaker_rate_based, get_circuit_state};
+    use std::time::Duration;
+
+    let _lock = CIRCUIT_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let call = |name: &str, ok: bool| -> Result<(), String> {
+        circuit_breaker_rate_based(name, 4, 0.5, 2, Duration::ZERO, || {
+            if ok {
+                Ok(())
+            } else {
+                Err("failed".to_string())
+            }
+        })
+    };
+
+    // Nothing trips until the window of four calls has filled
+    for _ in 0..3 {
+        let _ = call("recent:min_samples", false);
+        assert_eq!(
+            get_circuit_state("recent:min_samples"),
+            Some(CircuitState::Closed)
+        );
+    }
+    let _ = call("recent:min_samples", false);
+    assert_eq!(
+        get_circuit_state("recent:min_samples"),
+        Some(CircuitState::Open)
+    );
+
+    // Only the last four outcomes count: 2/4 failures stay at the threshold
+    for ok in [false, true, false, true, false] {
+        let _ = call("recent:ring", ok);
+        assert_eq!(get_circuit_state("recent:ring"), Some(CircuitState::Closed));
+    }
+    // The oldest failure is dropped, leaving 3/4
+    let _ = call("recent:ring", false);
+    assert_eq!(get_circuit_state("recent:ring"), Some(CircuitState::Open));
+
+    // Half-open recovery still needs `success_threshold` successes
+    assert_eq!(call("recent:ring", true), Ok(()));
+    assert_eq!(
+        get_circuit_state("recent:ring"),
+        Some(CircuitState::HalfOpen)
+    );
+    assert_eq!(call("recent:ring", true), Ok(()));
+    assert_eq!(get_circuit_state("recent:ring"), Some(CircuitState::Closed));
+}
+
+#[test]
+fn test_cache_key_from_memoizes_on_serializable_arguments() {
+    use decorate_macro::decorate;
+    use decorators::{cache_key_from, with_cache};
+    use serde::Serialize;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    #[derive(Serialize)]
+    struct SearchQuery {
+        term: &'static str,
+        page: u32,
+    }
+
+    static SEARCHES: AtomicU32 = AtomicU32::new(0);
+
+    let _lock = CACHE_CONFIG_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    #[decorate(with_cache(&cache_key_from(&query), Duration::from_secs(60)))]
+    fn search(query: SearchQuery) -> Result<String, String> {
+        SEARCHES.fetch_add(1, Ordering::SeqCst);
+        Ok(format!("{}#{}", query.term, query.page))
+    }
+
+    let first = SearchQuery {
+        term: "serde_key",
+        page: 1,
+    };
+    let second = SearchQuery {
+        term: "serde_key",
+        page: 2,
+    };
+    assert_ne!(cache_key_from(&first), cache_key_from(&second));
+
+    assert_eq!(search(first), Ok("serde_key#1".to_string()));
+    assert_eq!(search(second), Ok("serde_key#2".to_string()));
+    assert_eq!(
+        search(SearchQuery {
+            term: "serde_key",
+            page: 1
+        }),
+        Ok("serde_key#1".to_string())
+    );
+    assert_eq!(SEARCHES.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_backoff_if_returns_permanent_errors_immediately() {
+    use decorators::with_backoff_if;
+    use std::cell::Cell;
+    use std::time::{Duration, Instant};
+
+    let calls = Cell::new(0u32);
+    let start = Instant::now();
+    let result: Result<(), u16> = with_backoff_if(
+        5,
+        Duration::from_millis(200),
+        |status| *status >= 500,
+        || {
+            calls.set(calls.get() + 1);
+            Err(404)
+        },
+    );
+
+    assert_eq!(result, Err(404));
+    assert_eq!(calls.get(), 1);
+    assert!(start.elapsed() < Duration::from_millis(200));
+}
+
+#[test]
+fn test_backoff_if_retries_transient_errors() {
+    use decorators::with_backoff_if;
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    let calls = Cell::new(0u32);
+    let result: Result<&str, u16> = with_backoff_if(
+        5,
+        Duration::from_millis(1),
+        |status| *status >= 500,
+        || {
+            calls.set(calls.get() + 1);
+            if calls.get() < 3 {
+                Err(503)
+            } else {
+                Ok("page")
+            }
+        },
+    );
+
+    assert_eq!(result, Ok("page"));
+    assert_eq!(calls.get(), 3);
+}
+
+#[test]
+fn test_backoff_jitter_stays_within_bounds() {
+    use decorators::with_backoff_jitter_using;
+    use std::cell::RefCell;
+    use std::time::Duration;
+
+    let initial = Duration::from_millis(100);
+    let max_delay = Duration::from_millis(500);
+    let jitter = 0.2;
+    let sleeps = RefCell::new(Vec::new());
+
+    let result: Result<(), &str> = with_backoff_jitter_using(
+        50,
+        initial,
+        max_delay,
+        jitter,
+        |delay| sleeps.borrow_mut().push(delay),
+        rand::random::<f64>,
+        || Err("down"),
+    );
+
+    assert_eq!(result, Err("down"));
+    let sleeps = sleeps.into_inner();
+    assert_eq!(sleeps.len(), 49);
+    for (retry, delay) in sleeps.iter().enumerate() {
+        let base = (initial * 2u32.pow(retry.min(8) as u32)).min(max_delay);
+        assert!(*delay >= base.mul_f64(1.0 - jitter), "{retry}: {delay:?}");
+        assert!(*delay <= base.mul_f64(1.0 + jitter), "{retry}: {delay:?}");
+        assert!(*delay <= max_delay, "{retry}: {delay:?}");
+    }
+
+    // The extremes of the random range hit the jitter bounds exactly
+    let sleeps = RefCell::new(Vec::new());
+    let mut randoms = [0.0, 1.0].into_iter();
+    let _: Result<(), &str> = with_backoff_jitter_using(
+        3,
+        initial,
+        max_delay,
+        jitter,
+        |delay| sleeps.borrow_mut().push(delay),
+        || randoms.next().unwrap(),
+        || Err("down"),
+    );
+    assert_eq!(*sleeps.borrow(), [80, 240].map(Duration::from_millis));
+}
+
+#[test]
+fn test_retry_robust_caps_and_jitters_delays() {
+    use decorators::{with_retry_robust, with_retry_robust_using};
+    use std::cell::{Cell, RefCell};
+    use std::time::Duration;
+
+    let base = Duration::from_millis(100);
+    let cap = Duration::from_millis(500);
+    let sleeps = RefCell::new(Vec::new());
+    let randoms = Cell::new([1.0, 0.5, 1.0, 0.25, 1.0].into_iter());
+    let calls = Cell::new(0u32);
+
+    let result: Result<(), String> = with_retry_robust_using(
+        6,
+        base,
+        cap,
+        |delay| sleeps.borrow_mut().push(delay),
+        || {
+            let mut values = randoms.take();
+            let value = values.next().unwrap();
+            randoms.set(values);
+            value
+        },
+        || {
+            calls.set(calls.get() + 1);
+            Err(format!("failure {}", calls.get()))
+        },
+    );
+
+    assert_eq!(result, Err("failure 6".to_string()));
+    assert_eq!(calls.get(), 6);
+    // Ceilings 100, 200, 400, 500 (capped), 500 scaled by the random factors
+    let expected = [100, 100, 400, 125, 500].map(Duration::from_millis);
+    assert_eq!(*sleeps.borrow(), expected);
+
+    // The real clock and RNG stay within the capped ceiling
+    let result: Result<u32, &str> = with_retry_robust(
+        3,
+        Duration::from_millis(1),
+        Duration::from_millis(2),
+        || Err("down"),
+    );
+    assert_eq!(result, Err("down"));
+}
+
+#[test]
+fn test_rate_limit_try_returns_remaining_cooldown() {
+    use decorators::rate_limit_try;
+    use std::time::Duration;
+
+    assert_eq!(rate_limit_try("try:refresh", 1000, || 1), Ok(1));
+
+    let remaining = rate_limit_try("try:refresh", 1000, || 2).expect_err("called too soon");
+    assert!(remaining > Duration::from_millis(900), "{remaining:?}");
+    assert!(remaining <= Duration::from_millis(1000), "{remaining:?}");
+
+    // Other keys are limited independently
+    assert_eq!(rate_limit_try("try:other", 1000, || 3), Ok(3));
+}
+
+#[test]
+fn test_rate_limit_token_bucket_exhausts_and_refills() {
+    use decorators::rate_limit_token_bucket;
+    use std::time::Duration;
+
+    for call in 0..3 {
+        assert_eq!(
+            rate_limit_token_bucket("bucket:search", 20.0, 3, || call),
+            Some(call)
+        );
+    }
+    assert_eq!(
+        rate_limit_token_bucket("bucket:search", 20.0, 3, || 99),
+        None
+    );
+
+    // Other keys keep their own budget
+    assert_eq!(
+        rate_limit_token_bucket("bucket:upload", 20.0, 1, || 7),
+        Some(7)
+    );
+
+    // 20 tokens per second refills one token within 50ms
+    std::thread::sleep(Duration::from_millis(80));
+    assert_eq!(
+        rate_limit_token_bucket("bucket:search", 20.0, 3, || 4),
+        Some(4)
+    );
+}
+
+#[test]
+fn test_measure_time_exclusive_excludes_nested_calls() {
+    use decorate_macro::decorate;
+    use decorators::{get_exclusive_time, measure_time_exclusive};
+    use std::thread;
+    use std::time::Duration;
+
+    #[decorate(measure_time_exclusive("exclusive:inner"))]
+    fn inner() {
+        thread::sleep(Duration::from_millis(60));
+    }
+
+    #[decorate(measure_time_exclusive("exclusive:outer"))]
+    fn outer() {
+        thread::sleep(Duration::from_millis(20));
+        inner();
+    }
+
+    outer();
+
+    let outer_time = get_exclusive_time("exclusive:outer").expect("outer recorded");
+    let inner_time = get_exclusive_time("exclusive:inner").expect("inner recorded");
+    assert!(inner_time >= Duration::from_millis(60), "{inner_time:?}");
+    assert!(outer_time >= Duration::from_millis(20), "{outer_time:?}");
+    assert!(outer_time < Duration::from_millis(60), "{outer_time:?}");
+}
+
+#[test]
+fn test_cache_forever_never_expires_but_is_evictable() {
+    use decorators::{set_cache_max_size, with_cache, with_cache_forever};
+    use std::thread;
+    use std::time::Duration;
+
+    let _lock = CACHE_CONFIG_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let codes: Result<&str, ()> = with_cache_forever("forever:codes", || Ok("v1"));
+    assert_eq!(codes, Ok("v1"));
+    let short: Result<&str, ()> =
+        with_cache("forever:short", Duration::from_millis(1), || Ok("v1"));
+    assert_eq!(short, Ok("v1"));
+
+    thread::sleep(Duration::from_millis(20));
+
+    // The TTL entry expired, the forever entry did not
+    let short: Result<&str, ()> =
+        with_cache("forever:short", Duration::from_millis(1), || Ok("v2"));
+    assert_eq!(short, Ok("v2"));
+    let codes: Result<&str, ()> = with_cache_forever("forever:codes", || Ok("v2"));
+    assert_eq!(codes, Ok("v1"));
+
+    // Capacity eviction still applies
+    set_cache_max_size(1);
+    let _: Result<i32, ()> = with_cache_forever("forever:filler", || Ok(0));
+    let codes: Result<&str, ()> = with_cache_forever("forever:codes", || Ok("v3"));
+    assert_eq!(codes, Ok("v3"));
+    set_cache_max_size(1000);
+}
+
+#[test]
+fn test_circuit_breaker_if_ignores_unclassified_errors() {
+    use decorators::{CircuitState, circuit_breaker_if, get_circuit_state};
+
+    let _lock = CIRCUIT_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let call = |error: &str| -> Result<(), String> {
+        circuit_breaker_if(
+            "classified:api",
+            3,
+            1,
+            60,
+            |e: &String| e != "not found",
+            || Err(error.to_string()),
+        )
+    };
+
+    for _ in 0..10 {
+        assert_eq!(call("not found"), Err("not found".to_string()));
+    }
+    assert_eq!(
+        get_circuit_state("classified:api"),
+        Some(CircuitState::Closed)
+    );
+
+    for _ in 0..3 {
+        let _ = call("unavailable");
+    }
+    assert_eq!(
+        get_circuit_state("classified:api"),
+        Some(CircuitState::Open)
+    );
+}
+
+#[test]
+fn test_on_circuit_transition_reports_state_changes() {
+    use decorators::{CircuitState, circuit_breaker, on_circuit_transition};
+    use std::sync::{Arc, Mutex};
+
+    let _lock = CIRCUIT_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let transitions = Arc::new(Mutex::new(Vec::new()));
+    let seen = transitions.clone();
+    on_circuit_transition("alerting:api", move |from, to| {
+        seen.lock().unwrap().push((from, to));
+    });
+
+    let call = |ok: bool| -> Result<(), String> {
+        circuit_breaker("alerting:api", 3, 1, 0, || {
+            if ok {
+                Ok(())
+            } else {
+                Err("failed".to_string())
+            }
+        })
+    };
+
+    for _ in 0..2 {
+        let _ = call(false);
+    }
+    assert!(transitions.lock().unwrap().is_empty());
+
+    let _ = call(false);
+    assert_eq!(
+        *transitions.lock().unwrap(),
+        [(CircuitState::Closed, CircuitState::Open)]
+    );
+
+    // With a zero timeout the next call probes half-open and closes it again
+    assert_eq!(call(true), Ok(()));
+    assert_eq!(
+        *transitions.lock().unwrap(),
+        [
+            (CircuitState::Closed, CircuitState::Open),
+            (CircuitState::Open, CircuitState::HalfOpen),
+            (CircuitState::HalfOpen, CircuitState::Closed),
+        ]
+    );
+}
+
+#[test]
+fn test_on_circuit_transition_reports_each_change_once() {
+    use decorators::{
+        CircuitState, circuit_breaker, on_circuit_transition, reset_all_circuits, reset_circuit,
+    };
+    use std::sync::{Arc, Barrier, Mutex};
+    use std::thread;
+
+    let _lock = CIRCUIT_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let transitions = Arc::new(Mutex::new(Vec::new()));
+    let seen = transitions.clone();
+    on_circuit_transition("alerting:overlap", move |from, to| {
+        seen.lock().unwrap().push((from, to));
+    });
+
+    // Both calls are admitted while closed and both fail past the threshold
+    let barrier = Arc::new(Barrier::new(2));
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                circuit_breaker("alerting:overlap", 1, 1, 60, || -> Result<(), String> {
+                    barrier.wait();
+                    Err("failed".to_string())
+                })
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert!(handle.join().unwrap().is_err());
+    }
+    assert_eq!(
+        *transitions.lock().unwrap(),
+        [(CircuitState::Closed, CircuitState::Open)]
+    );
+
+    reset_circuit("alerting:overlap");
+    let _ = circuit_breaker("alerting:overlap", 1, 1, 60, || {
+        Err::<(), _>("failed".to_string())
+    });
+    reset_all_circuits();
+    assert_eq!(
+        *transitions.lock().unwrap(),
+        [
+            (CircuitState::Closed, CircuitState::Open),
+            (CircuitState::Open, CircuitState::Closed),
+            (CircuitState::Closed, CircuitState::Open),
+            (CircuitState::Open, CircuitState::Closed),
+        ]
+    );
+}
+
+#[test]
+fn test_circuit_breaker_fallback_serves_fallback_while_open() {
+    use decorators::{CircuitState, circuit_breaker_fallback, get_circuit_state};
+    use std::cell::Cell;
+
+    let _lock = CIRCUIT_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let calls = Cell::new(0);
+    let fetch = |ok: bool| -> Result<&str, &str> {
+        circuit_breaker_fallback(
+            "fallback:prices",
+            2,
+            1,
+            60,
+            || Ok("stale"),
+            || {
+                calls.set(calls.get() + 1);
+                if ok { Ok("fresh") } else { Err("unavailable") }
+            },
+        )
+    };
+
+    assert_eq!(fetch(true), Ok("fresh"));
+    assert_eq!(fetch(false), Err("unavailable"));
+    assert_eq!(fetch(false), Err("unavailable"));
+    assert_eq!(
+        get_circuit_state("fallback:prices"),
+        Some(CircuitState::Open)
+    );
+
+    // While open, the fallback is served and the body isn't called
+    assert_eq!(fetch(true), Ok("stale"));
+    assert_eq!(fetch(false), Ok("stale"));
+    assert_eq!(calls.get(), 3);
+    assert_eq!(
+        get_circuit_state("fallback:prices"),
+        Some(CircuitState::Open)
+    );
+}
+
+#[test]
+fn test_list_and_reset_all_circuits() {
+    use decorators::{CircuitState, circuit_breaker, list_circuits, reset_all_circuits};
+
+    let _lock = CIRCUIT_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let call = |name: &str, ok: bool| -> Result<(), String> {
+        circuit_breaker(name, 2, 1, 60, || {
+            if ok {
+                Ok(())
+            } else {
+                Err("failed".to_string())
+            }
+        })
+    };
+
+    let _ = call("bulk:healthy", true);
+    let _ = call("bulk:failing", false);
+    let _ = call("bulk:tripped", false);
+    let _ = call("bulk:tripped", false);
+
+    let bulk = |circuits: Vec<(String, CircuitState)>| -> Vec<(String, CircuitState)> {
+        circuits
+            .into_iter()
+            .filter(|(name, _)| name.starts_with("bulk:"))
+            .collect()
+    };
+    assert_eq!(
+        bulk(list_circuits()),
+        vec![
+            ("bulk:failing".to_string(), CircuitState::Closed),
+            ("bulk:healthy".to_string(), CircuitState::Closed),
+            ("bulk:tripped".to_string(), CircuitState::Open),
+        ]
+    );
+
+    reset_all_circuits();
+    assert!(
+        list_circuits()
+            .iter()
+            .all(|(_, state)| *state == CircuitState::Closed)
+    );
+
+    // Recorded failures are forgotten too, so one more failure doesn't trip it
+    let _ = call("bulk:failing", false);
+    assert!(call("bulk:tripped", true).is_ok());
+    assert!(
+        bulk(list_circuits())
+            .iter()
+            .all(|(_, state)| *state == CircuitState::Closed)
+    );
+}
+
+#[test]
+fn test_trace_calls_events_share_call_id() {
+    use decorate_macro::decorate;
+    use decorators::trace_calls_events;
+
+    #[decorate(trace_calls_events("double"))]
+    fn double(x: i32) -> i32 {
+        x * 2
+    }
+
+    let (_guard, log) = capture_tracing(Level::TRACE);
+
+    let outer = tracing::info_span!("outer");
+    outer.in_scope(|| {
+        assert_eq!(double(2), 4);
+        assert_eq!(double(5), 10);
+    });
+
+    let log = log.lock().unwrap();
+    let events: Vec<_> = log.iter().filter(|captured| !captured.is_span).collect();
+    let names: Vec<_> = events.iter().map(|event| event.name).collect();
+    assert_eq!(names, ["fn.enter", "fn.exit", "fn.enter", "fn.exit"]);
+    let call_ids: Vec<_> = events.iter().map(|event| event.field("call_id")).collect();
+    assert!(call_ids.iter().all(Option::is_some));
+    assert_eq!(call_ids[0], call_ids[1]);
+    assert_eq!(call_ids[2], call_ids[3]);
+    assert_ne!(call_ids[0], call_ids[2]);
+    assert!(events.iter().all(|event| event.is_root));
+}
+
+#[test]
+fn test_once_per_interval_skips_within_interval() {
+    use decorate_macro::decorate;
+    use decorators::{once_per_interval, reset_debounce};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static RUNS: AtomicU32 = AtomicU32::new(0);
+
+    #[decorate(once_per_interval("periodic:flush", 100))]
+    fn flush() -> Option<u32> {
+        RUNS.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    assert_eq!(flush(), Some(1));
+    assert_eq!(flush(), None);
+    assert_eq!(flush(), None);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+
+    std::thread::sleep(std::time::Duration::from_millis(120));
+    assert_eq!(flush(), Some(2));
+    assert_eq!(flush(), None);
+
+    // Resetting the key lets the next call run without waiting
+    reset_debounce("periodic:flush");
+    assert_eq!(flush(), Some(3));
+    assert_eq!(flush(), None);
+}
+
+#[test]
+fn test_cache_contains_and_entry_age() {
+    use decorators::{cache_contains, cache_entry_age, get_cache_stats, with_cache};
+    use std::time::Duration;
+
+    let _lock = CACHE_CONFIG_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    assert!(!cache_contains("inspect:value"));
+    assert_eq!(cache_entry_age("inspect:value"), None);
+
+    let value: Result<u32, ()> = with_cache("inspect:value", Duration::from_secs(60), || Ok(7));
+    assert_eq!(value, Ok(7));
+
+    let stats_before = get_cache_stats();
+    assert!(cache_contains("inspect:value"));
+    let age = cache_entry_age("inspect:value").expect("entry cached");
+    assert!(age < Duration::from_secs(1), "age was {age:?}");
+
+    // Inspection doesn't count as a cache access
+    let stats_after = get_cache_stats();
+    assert_eq!(stats_after.hits, stats_before.hits);
+    assert_eq!(stats_after.misses, stats_before.misses);
+}
+
+#[test]
+fn test_with_timeout_scoped_borrows_local_data() {
+    use decorate_macro::decorate;
+    use decorators::{TimeoutError, with_timeout_scoped};
+    use std::time::{Duration, Instant};
+
+    #[decorate(with_timeout_scoped(1000))]
+    fn total(values: &[u64]) -> Result<u64, TimeoutError<String>> {
+        values.iter().sum()
+    }
+
+    #[decorate(with_timeout_scoped(20))]
+    fn slow_len(text: &str) -> Result<usize, TimeoutError<String>> {
+        std::thread::sleep(Duration::from_millis(100));
+        text.len()
+    }
+
+    let values = vec![1, 2, 3, 4];
+    assert_eq!(total(&values).ok(), Some(10));
+
+    let text = String::from("borrowed");
+    let start = Instant::now();
+    assert!(matches!(
+        slow_len(&text),
+        Err(TimeoutError::Timeout { duration }) if duration == Duration::from_millis(20)
+    ));
+    // The scoped thread is joined even after the timeout
+    assert!(start.elapsed() >= Duration::from_millis(100));
+}
+
+#[test]
+fn test_with_timeout_cancellable_stops_cooperative_work() {
+    use decorators::{TimeoutError, with_timeout_cancellable};
+    use std::sync::atomic::Ordering;
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    assert_eq!(with_timeout_cancellable(1000, |_| 42).ok(), Some(42));
+
+    let (stopped_tx, stopped_rx) = mpsc::channel();
+    let result = with_timeout_cancellable(20, move |cancelled| {
+        let mut iterations = 0u32;
+        while !cancelled.load(Ordering::Relaxed) {
+            std::thread::sleep(Duration::from_millis(5));
+            iterations += 1;
+        }
+        let _ = stopped_tx.send(Instant::now());
+        iterations
+    });
+    let timed_out_at = Instant::now();
+    assert!(matches!(
+        result,
+        Err(TimeoutError::Timeout { duration }) if duration == Duration::from_millis(20)
+    ));
+
+    // The loop sees the flag and exits shortly after the timeout fires
+    let stopped_at = stopped_rx
+        .recv_timeout(Duration::from_secs(1))
+        .expect("cancelled loop should stop");
+    assert!(stopped_at.duration_since(timed_out_at) < Duration::from_millis(200));
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_with_timeout_async_times_out_slow_futures() {
+    use decorate_macro::decorate;
+    use decorators::{TimeoutError, with_timeout_async};
+    use std::time::Duration;
+
+    #[decorate(with_timeout_async(500))]
+    async fn fast(x: u32) -> Result<u32, TimeoutError<String>> {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        x * 2
+    }
+
+    #[decorate(with_timeout_async(20))]
+    async fn slow() -> Result<u32, TimeoutError<String>> {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        0
+    }
+
+    assert_eq!(fast(21).await.ok(), Some(42));
+    assert!(matches!(
+        slow().await,
+        Err(TimeoutError::Timeout { duration }) if duration == Duration::from_millis(20)
+    ));
+}
+
+#[test]
+fn test_with_timeout_result_reports_panics_as_errors() {
+    use decorators::{TimeoutError, with_timeout_result};
+
+    assert!(matches!(
+        with_timeout_result(1000, || Ok::<_, String>(7)),
+        Ok(7)
+    ));
+    assert!(matches!(
+        with_timeout_result(1000, || Err::<u32, _>("bad input".to_string())),
+        Err(TimeoutError::Inner(e)) if e == "bad input"
+    ));
+
+    let result = with_timeout_result(1000, || -> Result<u32, String> {
+        panic!("worker exploded");
+    });
+    assert!(matches!(
+        &result,
+        Err(TimeoutError::Panicked { payload }) if payload == "worker exploded"
+    ));
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Operation panicked: worker exploded"
+    );
+}
+
+#[test]
+fn test_with_timeout_reports_panics_as_panicked() {
+    use decorators::{TimeoutError, with_timeout};
+
+    let result = with_timeout(1000, || -> u32 { panic!("worker exploded") });
+    assert!(matches!(
+        result,
+        Err(TimeoutError::Panicked { payload }) if payload == "worker exploded"
+    ));
+}
+
+#[test]
+fn test_with_timeout_cancellable_reports_panics_as_panicked() {
+    use decorators::{TimeoutError, with_timeout_cancellable};
+
+    let result = with_timeout_cancellable(1000, |_| -> u32 { panic!("worker exploded") });
+    assert!(matches!(
+        result,
+        Err(TimeoutError::Panicked { payload }) if payload == "worker exploded"
+    ));
+}
+
+#[test]
+fn test_with_timeout_scoped_reports_panics_as_panicked() {
+    use decorators::{TimeoutError, with_timeout_scoped};
+
+    let data = [1u8, 2, 3];
+    let result = with_timeout_scoped(1000, || -> u32 {
+        panic!("worker exploded after {} bytes", data.len())
+    });
+    assert!(matches!(
+        result,
+        Err(TimeoutError::Panicked { payload }) if payload == "worker exploded after 3 bytes"
+    ));
+}
+
+#[test]
+fn test_with_timeout_metered_reports_panics_as_panicked() {
+    use decorators::{TimeoutError, get_timeout_stats, with_timeout_metered};
+
+    let result = with_timeout_metered("metered:panics", 1000, || -> u32 {
+        panic!("worker exploded")
+    });
+    assert!(matches!(
+        result,
+        Err(TimeoutError::Panicked { payload }) if payload == "worker exploded"
+    ));
+
+    let stats = get_timeout_stats("metered:panics").unwrap();
+    assert_eq!((stats.total, stats.succeeded, stats.timed_out), (1, 0, 0));
+}
+
+#[test]
+fn test_decorate_config_matches_attribute_form() {
+    use decorate_macro::decorate;
+    use decorators::DecorateConfig;
+    use std::sync::Mutex;
+
+    static ATTRIBUTE_EVENTS: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+    static RUNTIME_EVENTS: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+
+    fn clamp(x: i32, y: i32) -> (i32, i32) {
+        (x.max(0), y.max(0))
+    }
+
+    #[decorate(
+        transform_params = clamp,
+        pre = ATTRIBUTE_EVENTS.lock().unwrap().push("pre"),
+        post = ATTRIBUTE_EVENTS.lock().unwrap().push("post"),
+        transform_result = |r: i32| r * 10
+    )]
+    fn add(x: i32, y: i32) -> i32 {
+        ATTRIBUTE_EVENTS.lock().unwrap().push("body");
+        x + y
+    }
+
+    let config = DecorateConfig::<i32, (i32, i32)>::builder()
+        .transform_params(|(x, y)| clamp(x, y))
+        .pre(|| RUNTIME_EVENTS.lock().unwrap().push("pre"))
+        .post(|| RUNTIME_EVENTS.lock().unwrap().push("post"))
+        .transform_result(|r| r * 10)
+        .build();
+    let runtime_add = |x: i32, y: i32| {
+        config.call((x, y), |(x, y)| {
+            RUNTIME_EVENTS.lock().unwrap().push("body");
+            x + y
+        })
+    };
+
+    assert_eq!(add(-3, 4), 40);
+    assert_eq!(runtime_add(-3, 4), 40);
+    assert_eq!(*RUNTIME_EVENTS.lock().unwrap(), ["pre", "body", "post"]);
+    assert_eq!(
+        *ATTRIBUTE_EVENTS.lock().unwrap(),
+        *RUNTIME_EVENTS.lock().unwrap()
+    );
+}
+
+#[test]
+fn test_decorate_config_outer_stages() {
+    use decorators::DecorateConfig;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    let finally_runs = Arc::new(AtomicU32::new(0));
+    let misses = Arc::new(AtomicU32::new(0));
+    let config = DecorateConfig::<Option<i32>>::builder()
+        .finally({
+            let finally_runs = Arc::clone(&finally_runs);
+            move || {
+                finally_runs.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .on_none({
+            let misses = Arc::clone(&misses);
+            move || {
+                misses.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .catch(|_| Some(-1))
+        .around(|body| body().map(|n| n + 1))
+        .build();
+
+    assert_eq!(config.wrap(|| Some(1)), Some(2));
+    assert_eq!(config.wrap(|| None), None);
+    // `around` wraps `catch`, so it also sees the recovered value
+    assert_eq!(config.wrap(|| panic!("boom")), Some(0));
+    assert_eq!(finally_runs.load(Ordering::SeqCst), 3);
+    assert_eq!(misses.load(Ordering::SeqCst), 1);
+
+    let gated = DecorateConfig::<i32>::builder()
+        .transform_result(|r| r * 2)
+        .when(|| false)
+        .build();
+    assert_eq!(gated.wrap(|| 21), 21);
+}
+
+#[test]
+fn test_trace_calls_on_trait_method_with_self_bound() {
+    use decorate_macro::decorate;
+    use decorators::trace_calls;
+
+    trait Shape {
+        fn area(&self) -> i32;
+
+        // Without the retained bound, returning `Self` would make `dyn Shape` invalid
+        #[decorate(trace_calls)]
+        fn doubled(&self) -> Self
+        where
+            Self: Sized + Clone,
+        {
+            let mut copy = self.clone();
+            copy.scale(2);
+            copy
+        }
+
+        fn scale(&mut self, factor: i32);
+    }
+
+    #[derive(Clone)]
+    struct Square(i32);
+
+    impl Shape for Square {
+        fn area(&self) -> i32 {
+            self.0 * self.0
+        }
+
+        fn scale(&mut self, factor: i32) {
+            self.0 *= factor;
+        }
+    }
+
+    let square = Square(3).doubled();
+    assert_eq!(square.area(), 36);
+
+    let shapes: Vec<Box<dyn Shape>> = vec![Box::new(square), Box::new(Square(1))];
+    assert_eq!(shapes.iter().map(|shape| shape.area()).sum::<i32>(), 37);
+}
+
+#[test]
+fn test_cache_metrics_text_renders_current_stats() {
+    use decorators::{cache_metrics_text, get_cache_stats, with_cache};
+    use std::time::Duration;
+
+    let _lock = CACHE_CONFIG_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let expirations_before = get_cache_stats().expirations;
+    let _: Result<u32, ()> = with_cache("metrics:short", Duration::from_millis(1), || Ok(1));
+    std::thread::sleep(Duration::from_millis(5));
+    let _: Result<u32, ()> = with_cache("metrics:short", Duration::from_millis(1), || Ok(2));
+
+    let stats = get_cache_stats();
+    assert_eq!(stats.expirations, expirations_before + 1);
+
+    let text = cache_metrics_text();
+    for line in [
+        "# TYPE decorate_cache_hits_total counter".to_string(),
+        format!("decorate_cache_hits_total {}", stats.hits),
+        format!("decorate_cache_misses_total {}", stats.misses),
+        format!("decorate_cache_evictions_total {}", stats.evictions),
+        format!("decorate_cache_expirations_total {}", stats.expirations),
+        "# TYPE decorate_cache_size gauge".to_string(),
+        format!("decorate_cache_size {}", stats.size),
+        format!("decorate_cache_hit_rate {}", stats.hit_rate()),
+    ] {
+        assert!(
+            text.lines().any(|l| l == line),
+            "missing {line:?} in:\n{text}"
+        );
+    }
+    assert!(text.ends_with('\n'));
+}
+
+#[test]
+fn test_with_cache_computes_cold_key_once_under_contention() {
+    use decorators::with_cache;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Barrier};
+    use std::time::Duration;
+
+    let _lock = CACHE_CONFIG_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let runs = Arc::new(AtomicU32::new(0));
+    let barrier = Arc::new(Barrier::new(8));
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let runs = Arc::clone(&runs);
+            let barrier = Arc::clone(&barrier);
+            std::thread::spawn(move || {
+                barrier.wait();
+                with_cache("flight:report", Duration::from_secs(60), || {
+                    runs.fetch_add(1, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(50));
+                    Ok::<_, ()>("report")
+                })
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), Ok("report"));
+    }
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_with_cache_waiter_retries_after_failed_computation() {
+    use decorators::with_cache;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Barrier};
+    use std::time::Duration;
+
+    let _lock = CACHE_CONFIG_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    // The first computation fails, so one waiter computes again and the rest reuse it
+    let runs = Arc::new(AtomicU32::new(0));
+    let barrier = Arc::new(Barrier::new(4));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let runs = Arc::clone(&runs);
+            let barrier = Arc::clone(&barrier);
+            std::thread::spawn(move || {
+                barrier.wait();
+                with_cache("flight:flaky", Duration::from_secs(60), || {
+                    std::thread::sleep(Duration::from_millis(50));
+                    match runs.fetch_add(1, Ordering::SeqCst) {
+                        0 => Err("unavailable"),
+                        _ => Ok(7),
+                    }
+                })
+            })
+        })
+        .collect();
+
+    let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(
+        results.iter().filter(|r| **r == Err("unavailable")).count(),
+        1
+    );
+    assert_eq!(results.iter().filter(|r| **r == Ok(7)).count(), 3);
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_measure_time_on_associated_constructors() {
+    use decorate_macro::decorate;
+    use decorators::measure_time;
+
+    #[derive(Debug, PartialEq)]
+    struct Connection {
+        host: String,
+        attempts: u32,
+    }
+
+    impl Connection {
+        #[decorate(measure_time)]
+        fn new(host: &str) -> Self {
+            Self {
+                host: host.to_string(),
+                attempts: 0,
+            }
+        }
+
+        #[decorate(transform_result = |c: Self| Self { attempts: c.attempts + 1, ..c }, measure_time)]
+        fn localhost() -> Self {
+            Self::new("localhost")
+        }
+
+        #[decorate(retry_on_err = 2, measure_time)]
+        fn parse(spec: &str) -> Result<Self, String> {
+            let (host, attempts) = spec.split_once(':').ok_or("missing ':'")?;
+            Ok(Self {
+                host: host.to_string(),
+                attempts: attempts.parse().map_err(|_| "bad attempts")?,
+            })
+        }
+    }
+
+    assert_eq!(Connection::new("db").host, "db");
+    assert_eq!(
+        Connection::localhost(),
+        Connection {
+            host: "localhost".to_string(),
+            attempts: 1
+        }
+    );
+    assert_eq!(Connection::parse("cache:3").map(|c| c.attempts), Ok(3));
+    assert_eq!(Connection::parse("cache"), Err("missing ':'".to_string()));
+}
+
+#[test]
+fn test_trace_calls_max_depth_caps_spans() {
+    use decorate_macro::decorate;
+    use decorators::trace_calls_max_depth;
+
+    #[decorate(trace_calls_max_depth(3))]
+    fn countdown(n: u32) -> u32 {
+        if n == 0 { 0 } else { 1 + countdown(n - 1) }
+    }
+
+    let (_guard, log) = capture_tracing(Level::TRACE);
+    let spans = || {
+        log.lock()
+            .unwrap()
+            .iter()
+            .filter(|captured| captured.is_span)
+            .count()
+    };
+
+    assert_eq!(countdown(20), 20);
+    assert_eq!(spans(), 3);
+
+    // The depth is restored after each call
+    assert_eq!(countdown(1), 1);
+    assert_eq!(spans(), 5);
+}
+
+#[test]
+fn test_trace_calls_with_records_span_attributes() {
+    use decorate_macro::decorate;
+    use decorators::trace_calls_with;
+
+    #[decorate(trace_calls_with(&[("order_id", &order_id), ("user_id", &user_id)]))]
+    fn process_order(order_id: u64, user_id: &str) -> String {
+        format!("{user_id}:{order_id}")
+    }
+
+    let (_guard, log) = capture_tracing(Level::TRACE);
+
+    assert_eq!(process_order(42, "alice"), "alice:42");
+    assert_eq!(process_order(7, "bob"), "bob:7");
+    assert_eq!(
+        span_fields(&log, "attrs"),
+        ["order_id=42 user_id=alice", "order_id=7 user_id=bob"]
+    );
+}
+
+#[test]
+fn test_trace_calls_reports_call_site_through_track_caller() {
+    use decorate_macro::decorate;
+    use decorators::trace_calls;
+
+    #[track_caller]
+    #[decorate(trace_calls)]
+    fn checkout(items: u32) -> u32 {
+        items * 10
+    }
+
+    let (_guard, log) = capture_tracing(Level::TRACE);
+
+    let (first, second) = (line!() + 1, line!() + 2);
+    assert_eq!(checkout(1), 10);
+    assert_eq!(checkout(2), 20);
+    assert_eq!(
+        span_fields(&log, "line"),
+        [first.to_string(), second.to_string()]
+    );
+}
+
+#[test]
+fn test_cache_eviction_policies_choose_victims() {
+    use decorators::{
+        EvictionPolicy, cache_contains, clear_cache, set_cache_eviction_policy, set_cache_max_size,
+        with_cache,
+    };
+    use std::time::Duration;
+
+    let _lock = CACHE_CONFIG_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let ttl = Duration::from_secs(60);
+    let get = |key: &str| {
+        let _: Result<&str, ()> = with_cache(key, ttl, || Ok("value"));
+        std::thread::sleep(Duration::from_millis(2));
+    };
+
+    // `hot` is used most but least recently: LRU evicts it, LFU keeps it
+    for policy in [EvictionPolicy::Lru, EvictionPolicy::Lfu] {
+        clear_cache();
+        set_cache_max_size(3);
+        set_cache_eviction_policy(policy);
+
+        for key in ["policy:hot", "policy:hot", "policy:hot"] {
+            get(key);
+        }
+        get("policy:warm");
+        get("policy:warm");
+        get("policy:cold");
+        get("policy:new");
+
+        let evicted = match policy {
+            EvictionPolicy::Lru => "policy:hot",
+            EvictionPolicy::Lfu => "policy:cold",
+        };
+        for key in ["policy:hot", "policy:warm", "policy:cold", "policy:new"] {
+            assert_eq!(cache_contains(key), key != evicted, "{policy:?}: {key}");
+        }
+    }
+
+    // Among equally frequent entries, LFU evicts the least recently used
+    clear_cache();
+    get("policy:first");
+    get("policy:second");
+    get("policy:third");
+    get("policy:fourth");
+    assert!(!cache_contains("policy:first"));
+    assert!(cache_contains("policy:second"));
+
+    set_cache_eviction_policy(EvictionPolicy::Lru);
+    set_cache_max_size(1000);
+    clear_cache();
+}
+
+#[test]
+fn test_cache_instances_are_isolated() {
+    use decorators::{Cache, cache_contains};
+    use std::time::Duration;
+
+    let ttl = Duration::from_secs(60);
+    let users = Cache::new(10);
+    let orders = Cache::new(10);
+
+    assert_eq!(
+        users.get_or_compute("isolated:42", ttl, || Ok::<_, ()>("ada")),
+        Ok("ada")
+    );
+    assert_eq!(
+        orders.get_or_compute("isolated:42", ttl, || Ok::<_, ()>(3)),
+        Ok(3)
+    );
+
+    // Each instance answers from its own entry rather than recomputing
+    assert_eq!(
+        users.get_or_compute("isolated:42", ttl, || Ok::<_, ()>("other")),
+        Ok("ada")
+    );
+    assert_eq!(
+        orders.get_or_compute("isolated:42", ttl, || Ok::<_, ()>(0)),
+        Ok(3)
+    );
+    assert_eq!(users.stats().hits, 1);
+    assert_eq!(orders.stats().hits, 1);
+
+    // The shared cache behind `with_cache` is untouched
+    assert!(!cache_contains("isolated:42"));
+
+    users.invalidate("isolated:42");
+    assert!(!users.contains("isolated:42"));
+    assert!(orders.contains("isolated:42"));
+}
+
+#[test]
+fn test_cache_instance_sets_its_own_policy_and_pins() {
+    use decorators::{Cache, EvictionPolicy};
+    use std::time::Duration;
+
+    let ttl = Duration::from_secs(60);
+    let cache = Cache::new(2);
+    cache.set_eviction_policy(EvictionPolicy::Lfu);
+    let load = |key: &str| cache.get_or_compute(key, ttl, || Ok::<_, ()>(key.len()));
+
+    let _ = load("a");
+    let _ = load("bb");
+    let _ = load("a");
+    let _ = load("a");
+
+    // LFU evicts the rarely used entry even though `a` was cached first
+    let _ = load("ccc");
+    assert!(cache.contains("a"));
+    assert!(!cache.contains("bb"));
+
+    // A pinned entry is skipped, so the frequently used one goes instead
+    assert!(cache.pin("ccc"));
+    let _ = load("dddd");
+    assert!(!cache.contains("a"));
+    assert!(cache.contains("ccc"));
+    assert!(cache.contains("dddd"));
+
+    assert!(cache.unpin("ccc"));
+    assert!(!cache.pin("missing"));
+}
+
+#[test]
+fn test_cache_type_mismatch_is_a_miss() {
+    use decorators::Cache;
+    use std::time::Duration;
+
+    let ttl = Duration::from_secs(60);
+    let cache = Cache::new(10);
+
+    assert_eq!(
+        cache.get_or_compute("typed:7", ttl, || Ok::<_, ()>(7u32)),
+        Ok(7)
+    );
+    assert_eq!(
+        cache.get_or_compute("typed:7", ttl, || Ok::<_, ()>("seven")),
+        Ok("seven")
+    );
+    assert_eq!(cache.stats().hits, 0);
+}
+
+#[test]
+fn test_backoff_verbose_logs_total_delay() {
+    use decorators::with_backoff_verbose_using;
+    use std::cell::{Cell, RefCell};
+    use std::time::Duration;
+
+    let (_guard, log) = capture_tracing(Level::TRACE);
+    let logged = || -> Vec<u64> {
+        let log = log.lock().unwrap();
+        log.iter()
+            .filter_map(|captured| captured.field("total_delay_ms"))
+            .map(|total| total.parse().unwrap())
+            .collect()
+    };
+
+    let sleeps = RefCell::new(Vec::new());
+    let calls = Cell::new(0u32);
+    let result: Result<&str, &str> = with_backoff_verbose_using(
+        5,
+        Duration::from_millis(10),
+        |delay| sleeps.borrow_mut().push(delay),
+        || {
+            calls.set(calls.get() + 1);
+            if calls.get() < 4 {
+                Err("busy")
+            } else {
+                Ok("synced")
+            }
+        },
+    );
+    assert_eq!(result, Ok("synced"));
+
+    let slept: Duration = sleeps.borrow().iter().sum();
+    assert_eq!(slept, Duration::from_millis(10 + 20 + 40));
+    assert_eq!(logged(), [slept.as_millis() as u64]);
+
+    // Exhausted attempts log the total as well
+    sleeps.borrow_mut().clear();
+    let result: Result<(), &str> = with_backoff_verbose_using(
+        3,
+        Duration::from_millis(10),
+        |delay| sleeps.borrow_mut().push(delay),
+        || Err("down"),
+    );
+    assert_eq!(result, Err("down"));
+    assert_eq!(logged(), [70, 30]);
+}
+
+#[test]
+fn test_validate_input_all_reports_every_violation() {
+    use decorators::{ValidationRule, string_rules, validate_input_all};
+
+    const USERNAME_RULES: &[ValidationRule<String>] = &[
+        string_rules::NO_WHITESPACE,
+        ValidationRule::new(|s| s.len() >= 3, "Username must be at least 3 characters"),
+        string_rules::ALPHANUMERIC,
+    ];
+
+    let register = |username: &str| -> Result<String, Vec<String>> {
+        let username = username.to_string();
+        validate_input_all(&username, USERNAME_RULES, || Ok(username.clone()))
+    };
+
+    assert_eq!(register("alice"), Ok("alice".to_string()));
+    assert_eq!(
+        register("a b"),
+        Err(vec![
+            "String cannot contain whitespace".to_string(),
+            "String must be alphanumeric".to_string(),
+        ])
+    );
+}
+
+#[test]
+fn test_validate_input_typed_wraps_rule_message() {
+    use decorators::{ValidationRule, validate_input_typed};
+
+    #[derive(Debug, PartialEq)]
+    enum ApiError {
+        InvalidInput(String),
+        NotFound,
+    }
+
+    const ID_RULES: &[ValidationRule<u64>] = &[
+        ValidationRule::new(|id| *id > 0, "ID must be positive"),
+        ValidationRule::new(|id| *id < 1_000, "ID must be less than 1000"),
+    ];
+
+    let lookup = |id: u64| -> Result<&str, ApiError> {
+        validate_input_typed(
+            &id,
+            ID_RULES,
+            |message| ApiError::InvalidInput(message.to_string()),
+            || {
+                if id == 7 {
+                    Ok("alice")
+                } else {
+                    Err(ApiError::NotFound)
+                }
+            },
+        )
+    };
+
+    assert_eq!(lookup(7), Ok("alice"));
+    assert_eq!(lookup(8), Err(ApiError::NotFound));
+    assert_eq!(
+        lookup(0),
+        Err(ApiError::InvalidInput("ID must be positive".to_string()))
+    );
+    assert_eq!(
+        lookup(5_000),
+        Err(ApiError::InvalidInput(
+            "ID must be less than 1000".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_with_cache_tiered_serves_l1_hits_without_the_shared_lock() {
+    use decorators::{cache_lock_acquisitions, clear_cache, with_cache_tiered};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    static LOADS: AtomicU32 = AtomicU32::new(0);
+
+    let _lock = CACHE_CONFIG_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    clear_cache();
+
+    let load = || -> Result<String, String> {
+        with_cache_tiered("tiered:flags", Duration::from_secs(60), || {
+            LOADS.fetch_add(1, Ordering::SeqCst);
+            Ok("dark_mode".to_string())
+        })
+    };
+
+    assert_eq!(load(), Ok("dark_mode".to_string()));
+    assert_eq!(LOADS.load(Ordering::SeqCst), 1);
+
+    // Served from this thread's tier without touching the shared cache
+    let acquisitions = cache_lock_acquisitions();
+    for _ in 0..5 {
+        assert_eq!(load(), Ok("dark_mode".to_string()));
+    }
+    assert_eq!(cache_lock_acquisitions(), acquisitions);
+
+    // Another thread misses its own tier but hits the shared one
+    assert_eq!(
+        std::thread::spawn(load).join().unwrap(),
+        Ok("dark_mode".to_string())
+    );
+    assert!(cache_lock_acquisitions() > acquisitions);
+    assert_eq!(LOADS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_debounce_edge_leading_fires_once_per_burst() {
+    use decorators::{DebounceEdge, debounce_edge};
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    let burst = |key: &str, edge: DebounceEdge| -> Vec<bool> {
+        (0..10)
+            .map(|i| {
+                if i > 0 {
+                    sleep(Duration::from_millis(20));
+                }
+                debounce_edge(key, 100, edge, || ()).is_some()
+            })
+            .collect()
+    };
+
+    // Leading: the first call fires and every later call extends the burst
+    let leading = burst("edge:leading", DebounceEdge::Leading);
+    assert!(leading[0]);
+    assert_eq!(leading.iter().filter(|&&fired| fired).count(), 1);
+
+    // A pause of a full window starts a new burst
+    sleep(Duration::from_millis(120));
+    assert!(debounce_edge("edge:leading", 100, DebounceEdge::Leading, || ()).is_some());
+
+    // Trailing behaves like `debounce`: a cold first call fires, and calls fire
+    // again once the window has elapsed since the last executed one
+    let trailing = burst("edge:trailing", DebounceEdge::Trailing);
+    assert!(trailing[0]);
+    assert!(trailing.iter().filter(|&&fired| fired).count() >= 2);
+}
+
+#[test]
+fn test_with_cache_jittered_spreads_entry_expiry() {
+    use decorators::{cache_entry_ttl, clear_cache, with_cache, with_cache_jittered};
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    let _lock = CACHE_CONFIG_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    clear_cache();
+
+    let ttl = Duration::from_secs(60);
+    let jitter = Duration::from_secs(10);
+    for key in ["jitter:a", "jitter:b"] {
+        let _: Result<u32, ()> = with_cache_jittered(key, ttl, jitter, || Ok(1));
+    }
+    let _: Result<u32, ()> = with_cache("jitter:plain", ttl, || Ok(1));
+
+    let a = cache_entry_ttl("jitter:a").expect("jittered entry has its own TTL");
+    let b = cache_entry_ttl("jitter:b").expect("jittered entry has its own TTL");
+    for entry_ttl in [a, b] {
+        assert!(entry_ttl >= ttl - jitter && entry_ttl <= ttl + jitter);
+    }
+    assert_ne!(a, b);
+    assert_eq!(cache_entry_ttl("jitter:plain"), None);
+
+    // Entries expire by their own TTL: at most 90ms here
+    let computed = Cell::new(0);
+    let fetch = || -> Result<u32, ()> {
+        with_cache_jittered(
+            "jitter:short",
+            Duration::from_millis(50),
+            Duration::from_millis(40),
+            || {
+                computed.set(computed.get() + 1);
+                Ok(computed.get())
+            },
+        )
+    };
+    assert_eq!(fetch(), Ok(1));
+    std::thread::sleep(Duration::from_millis(100));
+    assert_eq!(fetch(), Ok(2));
+}
+
+#[test]
+fn test_debounce_max_wait_executes_under_continuous_calls() {
+    use decorators::{DebounceEdge, debounce_edge, debounce_max_wait};
+    use std::thread::sleep;
+    use std::time::{Duration, Instant};
+
+    let start = Instant::now();
+    let mut executed = Vec::new();
+    let mut starved = 0;
+    while start.elapsed() < Duration::from_millis(500) {
+        if debounce_max_wait("max_wait:search", 50, 100, || ()).is_some() {
+            executed.push(Instant::now());
+        }
+        if debounce_edge("max_wait:starved", 50, DebounceEdge::Leading, || ()).is_some() {
+            starved += 1;
+        }
+        sleep(Duration::from_millis(10));
+    }
+
+    // Pure leading-edge debouncing only ever runs the first call
+    assert_eq!(starved, 1);
+
+    // With a max wait, no gap between executions exceeds 100ms after the
+    // first skipped call, plus one call interval and scheduling slack
+    assert!(executed.len() >= 3, "executed {} times", executed.len());
+    for gap in executed.windows(2).map(|pair| pair[1] - pair[0]) {
+        assert!(gap <= Duration::from_millis(170), "gap of {gap:?}");
+    }
+}
+
+#[test]
+fn test_log_errors_dedup_suppresses_repeated_errors() {
+    use decorate_macro::decorate;
+    use decorators::log_errors_dedup;
+
+    #[decorate(log_errors_dedup)]
+    fn write_block(error: Option<&str>) -> Result<(), String> {
+        error.map_or(Ok(()), |e| Err(e.to_string()))
+    }
+
+    let (_guard, log) = capture_tracing(Level::TRACE);
+
+    for _ in 0..5 {
+        assert_eq!(write_block(Some("disk full")), Err("disk full".to_string()));
+    }
+    assert_eq!(messages(&log), ["❌ Operation failed"]);
+
+    // A different outcome flushes the summary of the suppressed repeats
+    assert_eq!(write_block(None), Ok(()));
+    assert_eq!(
+        messages(&log),
+        ["❌ Operation failed", "🔁 4 identical errors suppressed"]
+    );
+
+    // After the streak is broken, the same error is logged again
+    let _ = write_block(Some("disk full"));
+    let _ = write_block(Some("read only"));
+    assert_eq!(messages(&log).len(), 4);
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_validate_input_async_rejects_taken_username() {
+    use decorate_macro::decorate;
+    use decorators::{AsyncValidationRule, validate_input_async};
+    use std::sync::LazyLock;
+
+    /// Stands in for a database lookup.
+    async fn username_exists(name: &str) -> bool {
+        tokio::task::yield_now().await;
+        ["admin", "alice"].contains(&name)
+    }
+
+    static USERNAME_RULES: LazyLock<Vec<AsyncValidationRule<String>>> = LazyLock::new(|| {
+        vec![
+            AsyncValidationRule::<String>::new(
+                |name| Box::pin(async move { !name.is_empty() }),
+                "Username cannot be empty",
+            ),
+            AsyncValidationRule::<String>::new(
+                |name| Box::pin(async move { !username_exists(name).await }),
+                "Username is already taken",
+            ),
+        ]
+    });
+
+    #[decorate(validate_input_async(&username, &USERNAME_RULES))]
+    async fn register(username: String) -> Result<String, String> {
+        Ok(format!("registered {username}"))
+    }
+
+    assert_eq!(
+        register("bob".to_string()).await,
+        Ok("registered bob".to_string())
+    );
+    assert_eq!(
+        register("alice".to_string()).await,
+        Err("Username is already taken".to_string())
+    );
+    assert_eq!(
+        register(String::new()).await,
+        Err("Username cannot be empty".to_string())
+    );
+}
+
+#[test]
+fn test_with_semaphore_caps_concurrent_calls() {
+    use decorate_macro::decorate;
+    use decorators::with_semaphore;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    static RUNNING: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    #[decorate(with_semaphore("semaphore_cap", 2))]
+    fn query() {
+        let running = RUNNING.fetch_add(1, Ordering::SeqCst) + 1;
+        PEAK.fetch_max(running, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(20));
+        RUNNING.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    let handles: Vec<_> = (0..6).map(|_| thread::spawn(query)).collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(PEAK.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_with_semaphore_fair_grants_permits_in_request_order() {
+    use decorate_macro::decorate;
+    use decorators::with_semaphore_fair;
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+
+    static ORDER: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+    #[decorate(with_semaphore_fair("semaphore_fair", 1))]
+    fn export(id: u32, work_ms: u64) {
+        ORDER.lock().unwrap().push(id);
+        thread::sleep(Duration::from_millis(work_ms));
+    }
+
+    // Hold the only permit while the other callers queue up one after another
+    let holder = thread::spawn(|| export(0, 200));
+    thread::sleep(Duration::from_millis(30));
+
+    let mut waiters = Vec::new();
+    for id in 1..=5 {
+        waiters.push(thread::spawn(move || export(id, 5)));
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    holder.join().unwrap();
+    for waiter in waiters {
+        waiter.join().unwrap();
+    }
+
+    assert_eq!(*ORDER.lock().unwrap(), [0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_with_semaphore_treats_zero_permits_as_one() {
+    use decorators::{with_semaphore, with_semaphore_fair};
+
+    assert_eq!(with_semaphore("semaphore_zero", 0, || 1), 1);
+    assert_eq!(with_semaphore_fair("semaphore_zero_fair", 0, || 2), 2);
+}
+
+#[test]
+fn test_trace_calls_otel_creates_child_of_current_span() {
+    use decorate_macro::decorate;
+    use decorators::trace_calls_otel;
+    use opentelemetry::trace::{Span, TraceContextExt, Tracer};
+    use opentelemetry::{Context, Value, global};
+    use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider};
+
+    #[decorate(trace_calls_otel)]
+    fn charge(amount: u32) -> u32 {
+        amount * 2
+    }
+
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    global::set_tracer_provider(provider.clone());
+
+    let tracer = global::tracer("checkout");
+    let parent = tracer.start("checkout");
+    let parent_context = parent.span_context().clone();
+    {
+        let _attached = Context::current_with_span(parent).attach();
+        assert_eq!(charge(21), 42);
+        Context::current().span().end();
+    }
+
+    let spans = exporter.get_finished_spans().unwrap();
+    let child = spans.iter().find(|span| span.name == "fn_call").unwrap();
+    assert_eq!(child.parent_span_id, parent_context.span_id());
+    assert_eq!(child.span_context.trace_id(), parent_context.trace_id());
+    assert!(matches!(
+        child.attributes.iter().find(|kv| kv.key.as_str() == "call_id"),
+        Some(kv) if matches!(kv.value, Value::I64(_))
+    ));
+
+    provider.shutdown().unwrap();
+}
+
+#[test]
+fn test_measure_time_threshold_warns_only_on_slow_calls() {
+    use decorate_macro::decorate;
+    use decorators::measure_time_threshold;
+    use std::thread;
+    use std::time::Duration;
+
+    #[decorate(measure_time_threshold(Duration::from_millis(50)))]
+    fn lookup(delay_ms: u64) -> u64 {
+        thread::sleep(Duration::from_millis(delay_ms));
+        delay_ms
+    }
+
+    let (_guard, log) = capture_tracing(Level::TRACE);
+    let levels = || -> Vec<Level> {
+        let log = log.lock().unwrap();
+        let events = log.iter().filter(|captured| !captured.is_span);
+        events.map(|event| event.level).collect()
+    };
+
+    assert_eq!(lookup(0), 0);
+    assert_eq!(levels(), [Level::DEBUG]);
+
+    assert_eq!(lookup(80), 80);
+    assert_eq!(levels(), [Level::DEBUG, Level::WARN]);
+}
+
+#[test]
+fn test_max_duration_warns_and_still_returns_result() {
+    use decorate_macro::decorate;
+    use std::thread;
+    use std::time::Duration;
+
+    #[decorate(max_duration = Duration::from_millis(20))]
+    fn render(delay_ms: u64) -> String {
+        thread::sleep(Duration::from_millis(delay_ms));
+        format!("rendered in {delay_ms}ms")
+    }
+
+    let (_guard, log) = capture_tracing(Level::WARN);
+
+    assert_eq!(render(0), "rendered in 0ms");
+    assert!(messages(&log).is_empty());
+
+    assert_eq!(render(40), "rendered in 40ms");
+    assert_eq!(messages(&log), ["max_duration exceeded"]);
+}
+
+#[test]
+fn test_measure_time_named_records_latency_percentiles() {
+    use decorate_macro::decorate;
+    use decorators::{get_latency_stats, measure_time_named};
+    use std::thread;
+    use std::time::Duration;
+
+    #[decorate(measure_time_named("latency_percentiles"))]
+    fn handle(delay_ms: u64) -> u64 {
+        thread::sleep(Duration::from_millis(delay_ms));
+        delay_ms
+    }
+
+    assert!(get_latency_stats("latency_percentiles").is_none());
+    for delay_ms in [1, 2, 3, 4, 5, 6, 7, 8, 9, 30] {
+        assert_eq!(handle(delay_ms), delay_ms);
+    }
+
+    let stats = get_latency_stats("latency_percentiles").unwrap();
+    assert_eq!(stats.count, 10);
+    assert!(stats.min <= stats.p50 && stats.p50 <= stats.p99 && stats.p99 <= stats.max);
+    assert!(stats.min >= Duration::from_millis(1));
+    assert!(stats.p50 >= Duration::from_millis(5) && stats.p50 < Duration::from_millis(30));
+    assert!(stats.max >= Duration::from_millis(30));
+    assert!(stats.mean > stats.min && stats.mean < stats.max);
+}
+
+#[test]
+fn test_log_errors_sampled_logs_a_fraction_of_errors() {
+    use decorate_macro::decorate;
+    use decorators::log_errors_sampled;
+
+    #[decorate(log_errors_sampled(0.1))]
+    fn send_event(id: u32) -> Result<u32, String> {
+        Err(format!("collector unavailable for event {id}"))
+    }
+
+    let (_guard, log) = capture_tracing(Level::ERROR);
+
+    assert!(send_event(0).is_err());
+    assert_eq!(messages(&log).len(), 1, "the first error is always logged");
+
+    for id in 1..100 {
+        assert!(send_event(id).is_err());
+    }
+    let logged = messages(&log).len();
+    assert!((8..=12).contains(&logged), "logged {logged} of 100 errors");
+}
+
+#[test]
+fn test_with_retry_all_errors_returns_each_attempt_error_in_order() {
+    use decorators::with_retry_all_errors;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    let attempts = AtomicU32::new(0);
+    let sync_inventory = |succeed_on: u32| {
+        let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+        if attempt == succeed_on {
+            Ok("synced")
+        } else {
+            Err(format!("attempt {attempt} failed"))
+        }
+    };
+
+    assert_eq!(
+        with_retry_all_errors(3, || sync_inventory(0)),
+        Err(vec![
+            "attempt 1 failed".to_string(),
+            "attempt 2 failed".to_string(),
+            "attempt 3 failed".to_string(),
+        ])
+    );
+
+    attempts.store(0, Ordering::SeqCst);
+    assert_eq!(with_retry_all_errors(3, || sync_inventory(2)), Ok("synced"));
+}
+
+#[test]
+fn test_with_retry_all_errors_runs_once_with_zero_attempts() {
+    use decorators::with_retry_all_errors;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    let calls = AtomicU32::new(0);
+    let result: Result<(), Vec<u32>> =
+        with_retry_all_errors(0, || Err(calls.fetch_add(1, Ordering::SeqCst)));
+
+    assert_eq!(result, Err(vec![0]));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_log_errors_context_typed_preserves_error_variant() {
+    use decorate_macro::decorate;
+    use decorators::log_errors_context_typed;
+    use std::fmt;
+
+    #[derive(Debug, PartialEq)]
+    enum ConfigError {
+        NotFound(String),
+        Invalid { line: u32 },
+    }
+
+    impl fmt::Display for ConfigError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ConfigError::NotFound(path) => write!(f, "{path} not found"),
+                ConfigError::Invalid { line } => write!(f, "invalid entry on line {line}"),
+            }
+        }
+    }
+
+    #[decorate(log_errors_context_typed("loading config"))]
+    fn load_config(path: &str) -> Result<u32, ConfigError> {
+        match path {
+            "app.toml" => Ok(3),
+            "broken.toml" => Err(ConfigError::Invalid { line: 7 }),
+            _ => Err(ConfigError::NotFound(path.to_string())),
+        }
+    }
+
+    assert_eq!(load_config("app.toml"), Ok(3));
+    assert!(matches!(
+        load_config("missing.toml"),
+        Err(ConfigError::NotFound(path)) if path == "missing.toml"
+    ));
+    assert!(matches!(
+        load_config("broken.toml"),
+        Err(ConfigError::Invalid { line: 7 })
+    ));
+}
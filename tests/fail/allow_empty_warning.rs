@@ -0,0 +1,11 @@
+#![deny(deprecated)]
+
+use decorate_macro::decorate;
+
+// An empty list is accepted, but still warns that nothing is applied
+#[decorate(allow_empty)]
+fn add(x: i32, y: i32) -> i32 {
+    x + y
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+use decorate_macro::decorate;
+
+fn retry<F, R, E>(f: F) -> Result<R, E>
+where
+    F: Fn() -> Result<R, E>,
+{
+    f().or_else(|_| f())
+}
+
+struct Connection {
+    host: String,
+}
+
+impl Connection {
+    // Consuming `self` makes the body closure `FnOnce`, so it can't be retried
+    #[decorate(retry)]
+    fn into_host(self) -> Result<String, String> {
+        Ok(self.host)
+    }
+}
+
+fn main() {}
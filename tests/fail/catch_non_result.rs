@@ -0,0 +1,15 @@
+use decorate_macro::decorate;
+
+fn test_decorator<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    f()
+}
+
+#[decorate(catch = true, test_decorator)]
+fn divide(a: i32, b: i32) -> i32 {
+    a / b
+}
+
+fn main() {}
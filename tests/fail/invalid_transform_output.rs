@@ -0,0 +1,12 @@
+use decorate_macro::decorate;
+
+fn sum_params(x: i32, y: i32) -> i32 {
+    x + y
+}
+
+#[decorate(transform_params = sum_params)]
+fn add(x: i32, y: i32) -> i32 {
+    x + y
+}
+
+fn main() {}
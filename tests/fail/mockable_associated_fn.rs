@@ -0,0 +1,12 @@
+use decorate_macro::decorate;
+
+struct Client;
+
+impl Client {
+    #[decorate(mockable = true)]
+    fn new() -> Self {
+        Client
+    }
+}
+
+fn main() {}
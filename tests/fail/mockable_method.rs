@@ -0,0 +1,12 @@
+use decorate_macro::decorate;
+
+struct Client;
+
+impl Client {
+    #[decorate(mockable = true)]
+    fn fetch(&self, id: u32) -> u32 {
+        id
+    }
+}
+
+fn main() {}
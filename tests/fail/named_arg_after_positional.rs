@@ -0,0 +1,16 @@
+use decorate_macro::decorate;
+
+fn with_threshold<F, R>(min: i32, max: i32, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    assert!(min <= max, "Invalid threshold range");
+    f()
+}
+
+#[decorate(with_threshold(0, max = 100))]
+fn clamp(x: i32) -> i32 {
+    x
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+#![deny(deprecated)]
+
+use decorate_macro::decorate;
+
+fn with_threshold<F, R>(min: i32, max: i32, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    assert!(min <= max, "Invalid threshold range");
+    f()
+}
+
+// Names are not matched to parameters, so `max` is passed as the first argument
+#[decorate(with_threshold(max = 100, min = 0))]
+fn clamp(x: i32) -> i32 {
+    x
+}
+
+fn main() {}
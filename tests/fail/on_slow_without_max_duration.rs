@@ -0,0 +1,17 @@
+use decorate_macro::decorate;
+
+fn test_decorator<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    f()
+}
+
+fn report(_: std::time::Duration) {}
+
+#[decorate(on_slow = report, test_decorator)]
+fn lookup(id: u32) -> u32 {
+    id
+}
+
+fn main() {}
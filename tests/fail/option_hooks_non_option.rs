@@ -0,0 +1,15 @@
+use decorate_macro::decorate;
+
+fn test_decorator<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    f()
+}
+
+#[decorate(on_none = println!("missing"), test_decorator)]
+fn lookup(id: u32) -> Result<u32, String> {
+    Ok(id)
+}
+
+fn main() {}
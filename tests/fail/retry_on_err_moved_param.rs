@@ -0,0 +1,20 @@
+use decorate_macro::decorate;
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    f()
+}
+
+fn parse(input: String) -> Result<u32, std::num::ParseIntError> {
+    input.parse()
+}
+
+// Each attempt reruns the body, which cannot move `input` more than once
+#[decorate(retry_on_err = 2, log_execution)]
+fn parse_owned(input: String) -> Result<u32, std::num::ParseIntError> {
+    parse(input)
+}
+
+fn main() {}
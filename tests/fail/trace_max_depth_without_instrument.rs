@@ -0,0 +1,15 @@
+use decorate_macro::decorate;
+
+fn test_decorator<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    f()
+}
+
+#[decorate(trace_max_depth = 3, test_decorator)]
+fn countdown(n: u32) -> u32 {
+    if n == 0 { 0 } else { countdown(n - 1) }
+}
+
+fn main() {}
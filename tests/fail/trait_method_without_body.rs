@@ -0,0 +1,15 @@
+use decorate_macro::decorate;
+
+fn test_decorator<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    f()
+}
+
+trait Shape {
+    #[decorate(test_decorator)]
+    fn area(&self) -> i32;
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use decorate_macro::decorate;
+
+struct Counter {
+    value: i32,
+}
+
+fn clamp(step: i32) -> i32 {
+    step.max(0)
+}
+
+impl Counter {
+    #[decorate(transform_params = clamp)]
+    fn increment(&mut self, step: i32) {
+        self.value += step;
+    }
+}
+
+fn main() {}
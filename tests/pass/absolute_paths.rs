@@ -0,0 +1,27 @@
+use decorate_macro::decorate;
+
+// Leading-`::` paths name an external crate from the crate root, as macro-generated
+// code usually does
+#[decorate(::std::panic::catch_unwind)]
+fn checked_div(a: i32, b: i32) -> std::thread::Result<i32> {
+    a / b
+}
+
+macro_rules! guarded {
+    ($name:ident, $value:expr) => {
+        #[decorate(transform_result = ::std::convert::identity, ::std::panic::catch_unwind)]
+        fn $name() -> ::std::thread::Result<u32> {
+            $value
+        }
+    };
+}
+
+guarded!(forty_two, 42);
+
+fn main() {
+    std::panic::set_hook(Box::new(|_| {}));
+
+    assert_eq!(checked_div(6, 3).ok(), Some(2));
+    assert!(checked_div(1, 0).is_err());
+    assert_eq!(forty_two().ok(), Some(42));
+}
@@ -0,0 +1,44 @@
+use decorate_macro::decorate;
+
+fn double_result<F>(f: F) -> i32
+where
+    F: FnOnce() -> i32,
+{
+    f() * 2
+}
+
+// With `allow_empty`, an empty list leaves the function unchanged; the warning
+// it raises is expected here
+#[allow(deprecated)]
+#[decorate(allow_empty)]
+fn add(x: i32, y: i32) -> i32 {
+    x + y
+}
+
+// Decorators after the flag are applied as usual
+#[decorate(allow_empty, double_result)]
+fn add_doubled(x: i32, y: i32) -> i32 {
+    x + y
+}
+
+// Generated code can splice a possibly empty list after the flag, leaving a
+// trailing comma when it is empty
+macro_rules! decorated {
+    ($name:ident, [$($decorator:path),*]) => {
+        #[allow(deprecated)]
+        #[decorate(allow_empty, $($decorator),*)]
+        fn $name(x: i32) -> i32 {
+            x + 1
+        }
+    };
+}
+
+decorated!(plain_increment, []);
+decorated!(doubled_increment, [double_result]);
+
+fn main() {
+    assert_eq!(add(2, 3), 5);
+    assert_eq!(add_doubled(2, 3), 10);
+    assert_eq!(plain_increment(1), 2);
+    assert_eq!(doubled_increment(1), 4);
+}
@@ -0,0 +1,52 @@
+use decorate_macro::decorate;
+use std::cell::RefCell;
+
+thread_local! {
+    static EVENTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(event: String) {
+    EVENTS.with(|events| events.borrow_mut().push(event));
+}
+
+fn timed<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+    R: std::fmt::Debug,
+{
+    let start = std::time::Instant::now();
+    let result = f();
+    record(format!("{:?} after {:?}", result, start.elapsed()));
+    result
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+#[decorate(around = timed, transform_result = double, log_execution)]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[decorate(around = |f| f() * 10, transform_result = |r| r + 1)]
+fn identity(x: i32) -> i32 {
+    x
+}
+
+fn main() {
+    // `around` sees the transformed result
+    assert_eq!(add(2, 3), 10);
+    assert_eq!(EVENTS.with(|events| events.borrow().len()), 1);
+    assert!(EVENTS.with(|events| events.borrow()[0].starts_with("10 after")));
+
+    assert_eq!(identity(2), 30);
+}
@@ -0,0 +1,43 @@
+use decorate_macro::decorate;
+use std::future::Future;
+
+// Async closures created and awaited inside a decorated async body, including
+// ones that borrow locals and parameters, are left untouched by the wrapping.
+fn log_call<F, Fut, R>(f: F) -> impl Future<Output = R>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = R>,
+{
+    async move {
+        let result = f().await;
+        println!("Async function completed");
+        result
+    }
+}
+
+#[decorate(log_call)]
+async fn sum_doubled(values: Vec<u32>) -> u32 {
+    let double = async |x: u32| {
+        tokio::task::yield_now().await;
+        x * 2
+    };
+    let mut total = 0;
+    for value in &values {
+        total += double(*value).await;
+    }
+    total
+}
+
+#[decorate(log_call, transform_result = |n: usize| n + 1)]
+async fn count_matching(words: &[&str], prefix: &str) -> usize {
+    let matches = async || words.iter().filter(|w| w.starts_with(prefix)).count();
+    matches().await
+}
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        assert_eq!(sum_doubled(vec![1, 2, 3]).await, 12);
+        assert_eq!(count_matching(&["apple", "avocado", "pear"], "a").await, 3);
+    });
+}
@@ -0,0 +1,57 @@
+use decorate_macro::decorate;
+use std::future::Future;
+use std::num::ParseIntError;
+
+#[derive(Debug, PartialEq)]
+enum AppError {
+    InvalidNumber(String),
+}
+
+impl From<ParseIntError> for AppError {
+    fn from(e: ParseIntError) -> Self {
+        AppError::InvalidNumber(e.to_string())
+    }
+}
+
+fn into_app_error(result: Result<i32, ParseIntError>) -> Result<i32, AppError> {
+    result.map_err(AppError::from)
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+// In an `async fn`, `?` propagates into the transform's input error type
+#[decorate(transform_result_try = into_app_error, log_execution)]
+async fn parse_and_double(input: &str) -> Result<i32, AppError> {
+    tokio::task::yield_now().await;
+    let value: i32 = input.trim().parse()?;
+    Ok(value * 2)
+}
+
+fn keep_app_error(result: Result<i32, AppError>) -> Result<i32, AppError> {
+    result.map(|value| value + 1)
+}
+
+// For `impl Future`, the transform maps the awaited output
+#[decorate(transform_result_try = keep_app_error, log_execution)]
+fn parse_later(input: String) -> impl Future<Output = Result<i32, AppError>> {
+    async move { Ok(input.trim().parse::<i32>()?) }
+}
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        assert_eq!(parse_and_double(" 21 ").await, Ok(42));
+        assert!(matches!(
+            parse_and_double("abc").await,
+            Err(AppError::InvalidNumber(_))
+        ));
+        assert_eq!(parse_later("41".to_string()).await, Ok(42));
+        assert!(parse_later("x".to_string()).await.is_err());
+    });
+}
@@ -0,0 +1,49 @@
+use decorate_macro::decorate;
+use std::cell::RefCell;
+
+thread_local! {
+    static EVENTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(event: String) {
+    EVENTS.with(|events| events.borrow_mut().push(event));
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+#[decorate(
+    pre = {
+        let total = a + b;
+        record(format!("pre {} sum={}", __fn_name, total));
+    },
+    post = {
+        let label = "post";
+        record(label.to_string());
+    },
+    finally = { record("finally".into()) },
+    log_execution
+)]
+fn add(a: i32, b: i32) -> i32 {
+    record("body".into());
+    a + b
+}
+
+#[decorate(pre = record("single".into()), log_execution)]
+fn single() -> i32 {
+    1
+}
+
+fn main() {
+    assert_eq!(add(2, 3), 5);
+    assert_eq!(single(), 1);
+    assert_eq!(
+        EVENTS.with(|events| events.borrow().clone()),
+        ["pre add sum=5", "body", "post", "finally", "single"]
+    );
+}
@@ -0,0 +1,60 @@
+use decorate_macro::decorate;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+static COMPLETED: AtomicUsize = AtomicUsize::new(0);
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+// A decorator aware of boxed futures: it adds behavior after completion
+// and returns the same boxed type rather than boxing again.
+fn count_completion<F, T>(f: F) -> BoxFuture<T>
+where
+    F: FnOnce() -> BoxFuture<T>,
+    T: Send + 'static,
+{
+    let future = f();
+    Box::pin(async move {
+        let result = future.await;
+        COMPLETED.fetch_add(1, Ordering::SeqCst);
+        result
+    })
+}
+
+#[decorate(log_execution)]
+fn boxed_answer() -> Pin<Box<dyn Future<Output = i32> + Send>> {
+    Box::pin(async { 42 })
+}
+
+#[decorate(log_execution, count_completion)]
+fn boxed_double(x: i32) -> Pin<Box<dyn Future<Output = i32> + Send>> {
+    Box::pin(async move { x * 2 })
+}
+
+#[decorate(log_execution)]
+fn make_adder(n: i32) -> Box<dyn Fn(i32) -> i32> {
+    Box::new(move |x| x + n)
+}
+
+fn main() {
+    // Annotating the binding checks the decorated functions return exactly
+    // the declared single-boxed future.
+    let answer: Pin<Box<dyn Future<Output = i32> + Send>> = boxed_answer();
+    let doubled: Pin<Box<dyn Future<Output = i32> + Send>> = boxed_double(21);
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    assert_eq!(runtime.block_on(answer), 42);
+    assert_eq!(runtime.block_on(doubled), 42);
+    assert_eq!(COMPLETED.load(Ordering::SeqCst), 1);
+
+    assert_eq!(make_adder(2)(40), 42);
+}
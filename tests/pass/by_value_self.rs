@@ -0,0 +1,53 @@
+use decorate_macro::decorate;
+
+fn log_call<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let result = f();
+    println!("call finished");
+    result
+}
+
+fn traced<F, R>(label: &str, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("{label}: start");
+    f()
+}
+
+struct Request {
+    method: String,
+    body: Vec<u8>,
+}
+
+impl Request {
+    // `self` is moved into the body closure, which `FnOnce` decorators accept
+    #[decorate(log_call)]
+    fn into_parts(self) -> (String, Vec<u8>) {
+        (self.method, self.body)
+    }
+
+    #[decorate(log_call, transform_result = |len: usize| len * 2)]
+    fn into_body_len(mut self) -> usize {
+        self.body.push(0);
+        self.body.len()
+    }
+
+    #[decorate(traced("consume"))]
+    fn consume(self) -> String {
+        self.method
+    }
+}
+
+fn main() {
+    let request = || Request {
+        method: "POST".to_string(),
+        body: vec![1, 2, 3],
+    };
+
+    assert_eq!(request().into_parts(), ("POST".to_string(), vec![1, 2, 3]));
+    assert_eq!(request().into_body_len(), 8);
+    assert_eq!(request().consume(), "POST");
+}
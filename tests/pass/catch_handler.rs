@@ -0,0 +1,47 @@
+use decorate_macro::decorate;
+use std::any::Any;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static POST_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+fn fallback_config(_payload: Box<dyn Any + Send>) -> String {
+    "default".to_string()
+}
+
+#[decorate(catch = fallback_config, log_execution)]
+fn load_config(path: &str) -> String {
+    if path.is_empty() {
+        panic!("no config path");
+    }
+    format!("loaded {path}")
+}
+
+#[decorate(
+    post = POST_RUNS.fetch_add(1, Ordering::SeqCst),
+    catch = |_| -1,
+    log_execution
+)]
+fn checked_index(values: &[i32], index: usize) -> i32 {
+    values[index]
+}
+
+fn main() {
+    // Keep the expected panics out of the test output
+    std::panic::set_hook(Box::new(|_| {}));
+
+    assert_eq!(load_config("app.toml"), "loaded app.toml");
+    assert_eq!(load_config(""), "default");
+
+    assert_eq!(checked_index(&[1, 2, 3], 1), 2);
+    assert_eq!(checked_index(&[1, 2, 3], 10), -1);
+    // `post` is skipped when the body panics
+    assert_eq!(POST_RUNS.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,58 @@
+use decorate_macro::decorate;
+use std::any::Any;
+
+#[derive(Debug, PartialEq)]
+enum AppError {
+    Panicked(String),
+}
+
+impl From<Box<dyn Any + Send>> for AppError {
+    fn from(payload: Box<dyn Any + Send>) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        AppError::Panicked(message)
+    }
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+#[decorate(catch = true, log_execution)]
+fn divide(a: i32, b: i32) -> Result<i32, AppError> {
+    if b == 0 {
+        panic!("division by zero");
+    }
+    Ok(a / b)
+}
+
+#[decorate(
+    map_panic = |_payload: Box<dyn Any + Send>| "parser crashed".to_string(),
+    log_execution
+)]
+fn parse_digit(input: &str) -> Result<u32, String> {
+    let digit = input.chars().next().unwrap().to_digit(10);
+    digit.ok_or_else(|| format!("not a digit: {input}"))
+}
+
+fn main() {
+    // Keep the expected panics out of the test output
+    std::panic::set_hook(Box::new(|_| {}));
+
+    assert_eq!(divide(10, 2), Ok(5));
+    assert_eq!(
+        divide(1, 0),
+        Err(AppError::Panicked("division by zero".to_string()))
+    );
+
+    assert_eq!(parse_digit("7"), Ok(7));
+    assert_eq!(parse_digit("x"), Err("not a digit: x".to_string()));
+    assert_eq!(parse_digit(""), Err("parser crashed".to_string()));
+}
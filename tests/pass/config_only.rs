@@ -0,0 +1,32 @@
+use decorate_macro::decorate;
+
+fn clamp(x: i32, y: i32) -> (i32, i32) {
+    (x.max(0), y.max(0))
+}
+
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+#[decorate(transform_params = clamp)]
+fn add(x: i32, y: i32) -> i32 {
+    x + y
+}
+
+#[decorate(log_execution, transform_params = double)]
+fn increment(x: i32) -> i32 {
+    x + 1
+}
+
+fn main() {
+    assert_eq!(add(-5, 3), 3);
+    assert_eq!(increment(4), 9);
+}
@@ -0,0 +1,45 @@
+use decorate_macro::decorate;
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+const BASE_COST: u64 = 10;
+
+#[decorate(cost = 250, log_execution)]
+fn render_page(id: u32) -> String {
+    format!("page {id}")
+}
+
+/// Sums a slice.
+#[decorate(cost = BASE_COST * 4)]
+pub fn checksum(data: &[u8]) -> u32 {
+    data.iter().map(|&b| b as u32).sum()
+}
+
+struct Index;
+
+impl Index {
+    #[decorate(cost = 1_000, log_execution)]
+    fn rebuild(&self) -> usize {
+        42
+    }
+}
+
+// Evaluated at compile time
+const _: () = assert!(render_page_cost() == 250);
+
+fn main() {
+    assert_eq!(render_page(3), "page 3");
+    assert_eq!(render_page_cost(), 250);
+
+    assert_eq!(checksum(&[1, 2, 3]), 6);
+    assert_eq!(checksum_cost(), 40);
+
+    assert_eq!(Index.rebuild(), 42);
+    assert_eq!(Index::rebuild_cost(), 1_000);
+}
@@ -0,0 +1,58 @@
+use decorate_macro::decorate;
+use std::borrow::Cow;
+
+fn measure_time<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let start = std::time::Instant::now();
+    let result = f();
+    println!("Took {:?}", start.elapsed());
+    result
+}
+
+fn ensure_trailing_slash(path: Cow<'_, str>) -> Cow<'_, str> {
+    if path.ends_with('/') {
+        path
+    } else {
+        Cow::Owned(format!("{path}/"))
+    }
+}
+
+#[decorate(transform_result = ensure_trailing_slash, measure_time)]
+fn normalize(path: &str) -> Cow<'_, str> {
+    if path.contains('\\') {
+        Cow::Owned(path.replace('\\', "/"))
+    } else {
+        Cow::Borrowed(path)
+    }
+}
+
+#[decorate(transform_result = |c: Cow<'a, str>| c, measure_time)]
+fn pick<'a>(primary: &'a str, fallback: &'a str) -> Cow<'a, str> {
+    if primary.is_empty() {
+        Cow::Borrowed(fallback)
+    } else {
+        Cow::Borrowed(primary)
+    }
+}
+
+#[decorate(transform_result = |r| r.map(|s| s.trim()), measure_time)]
+fn first_word(text: &str) -> Result<&str, String> {
+    text.split_whitespace()
+        .next()
+        .ok_or_else(|| "empty".to_string())
+}
+
+fn main() {
+    let input = String::from("a/b");
+    assert!(matches!(normalize("a/b/"), Cow::Borrowed("a/b/")));
+    assert_eq!(normalize(&input), "a/b/");
+    assert_eq!(normalize("a\\b"), "a/b/");
+
+    assert_eq!(pick("", "fallback"), "fallback");
+    assert_eq!(pick("main", "fallback"), "main");
+
+    assert_eq!(first_word("  hello world"), Ok("hello"));
+    assert_eq!(first_word("   "), Err("empty".to_string()));
+}
@@ -0,0 +1,85 @@
+use decorate_macro::decorate;
+use std::cell::RefCell;
+
+thread_local! {
+    static LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn log(line: String) {
+    LOG.with(|log| log.borrow_mut().push(line));
+}
+
+// A factory returning the decorator; `F` is inferred from the wrapped body
+fn make_logger<F, R>(ctx: &'static str) -> impl FnOnce(F) -> R
+where
+    F: FnOnce() -> R,
+{
+    move |f| {
+        log(format!("[{ctx}] calling"));
+        f()
+    }
+}
+
+// The returned decorator can take arguments of its own
+fn make_scaler<F>(unit: &'static str) -> impl FnOnce(i32, F) -> i32
+where
+    F: FnOnce() -> i32,
+{
+    move |factor, f| {
+        log(format!("scaling by {factor}{unit}"));
+        f() * factor
+    }
+}
+
+struct Service;
+
+impl Service {
+    fn decorator<F: FnOnce() -> R, R>(&self) -> impl FnOnce(F) -> R {
+        |f| {
+            log("service".to_string());
+            f()
+        }
+    }
+
+    #[decorate((self.decorator()))]
+    fn handle(&self, x: u32) -> u32 {
+        x + 1
+    }
+}
+
+#[decorate((make_logger("math")))]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[decorate((make_scaler("x"))(3), (make_logger("outer")))]
+fn seven() -> i32 {
+    7
+}
+
+#[decorate((make_logger("async")))]
+async fn fetch(id: u32) -> u32 {
+    id * 2
+}
+
+fn main() {
+    assert_eq!(add(2, 3), 5);
+    assert_eq!(seven(), 21);
+    assert_eq!(Service.handle(1), 2);
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    assert_eq!(runtime.block_on(fetch(4)), 8);
+
+    LOG.with(|log| {
+        assert_eq!(
+            *log.borrow(),
+            [
+                "[math] calling",
+                "scaling by 3x",
+                "[outer] calling",
+                "service",
+                "[async] calling",
+            ]
+        );
+    });
+}
@@ -0,0 +1,72 @@
+use decorate_macro::decorate;
+
+struct Document {
+    title: String,
+}
+
+fn measure_time<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let start = std::time::Instant::now();
+    let result = f();
+    println!("Took {:?}", start.elapsed());
+    result
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+fn trim_result(s: &str) -> &str {
+    s.trim()
+}
+
+#[decorate(measure_time)]
+fn first(s: &str) -> &str {
+    &s[..1]
+}
+
+#[decorate(transform_result = trim_result, measure_time, log_execution)]
+fn head(s: &str, n: usize) -> &str {
+    &s[..n]
+}
+
+#[decorate(post = println!("split"), log_execution)]
+fn split_once_or_all(s: &str) -> (&str, Option<&str>) {
+    match s.split_once(':') {
+        Some((a, b)) => (a, Some(b)),
+        None => (s, None),
+    }
+}
+
+#[decorate(transform_result = |r| r.trim_end(), log_execution)]
+fn tail(s: &str, n: usize) -> &str {
+    &s[n..]
+}
+
+impl Document {
+    #[decorate(measure_time)]
+    fn title(&self) -> &str {
+        &self.title
+    }
+}
+
+fn main() {
+    let owned = String::from("hello");
+    assert_eq!(first(&owned), "h");
+    assert_eq!(head("  ab  ", 4), "ab");
+    assert_eq!(tail("abc  ", 1), "bc");
+    assert_eq!(split_once_or_all("a:b"), ("a", Some("b")));
+    assert_eq!(split_once_or_all("ab"), ("ab", None));
+
+    let doc = Document {
+        title: "Report".to_string(),
+    };
+    let title = doc.title();
+    assert_eq!(title, "Report");
+}
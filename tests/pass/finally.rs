@@ -0,0 +1,56 @@
+use decorate_macro::decorate;
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static FINALLY_RAN: Cell<bool> = const { Cell::new(false) };
+    static EVENTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(event: &str) {
+    EVENTS.with(|events| events.borrow_mut().push(event.to_string()));
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+#[decorate(
+    post = record("post"),
+    finally = FINALLY_RAN.with(|ran| ran.set(true)),
+    log_execution
+)]
+fn explode() -> i32 {
+    panic!("boom")
+}
+
+#[decorate(
+    post = record("post"),
+    finally = record(&format!("finally {}", __fn_name)),
+    log_execution
+)]
+fn add(a: i32, b: i32) -> i32 {
+    record("body");
+    a + b
+}
+
+#[decorate(finally = record(&format!("cleanup {}", id)))]
+fn lookup(id: u32) -> Option<u32> {
+    (id > 0).then_some(id * 10)
+}
+
+fn main() {
+    let result = std::panic::catch_unwind(explode);
+    assert!(result.is_err());
+    assert!(FINALLY_RAN.with(Cell::get));
+
+    assert_eq!(add(2, 3), 5);
+    assert_eq!(lookup(4), Some(40));
+    assert_eq!(
+        EVENTS.with(|events| events.borrow().clone()),
+        ["body", "post", "finally add", "cleanup 4"]
+    );
+}
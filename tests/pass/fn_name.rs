@@ -0,0 +1,39 @@
+use decorate_macro::decorate;
+use std::sync::Mutex;
+
+static LOG: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+#[decorate(
+    pre = LOG.lock().unwrap().push(format!("enter {}", __fn_name)),
+    post = LOG.lock().unwrap().push(format!("exit {}", __fn_name)),
+    log_execution
+)]
+fn checkout(total: u32) -> u32 {
+    total + 1
+}
+
+struct Cart;
+
+impl Cart {
+    #[decorate(post = LOG.lock().unwrap().push(format!("exit {}", __fn_name)), log_execution)]
+    fn clear(&self) -> usize {
+        0
+    }
+}
+
+fn main() {
+    assert_eq!(checkout(41), 42);
+    assert_eq!(Cart.clear(), 0);
+    assert_eq!(
+        *LOG.lock().unwrap(),
+        ["enter checkout", "exit checkout", "exit clear"]
+    );
+}
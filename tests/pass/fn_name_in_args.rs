@@ -0,0 +1,48 @@
+use decorate_macro::decorate;
+use std::cell::RefCell;
+
+thread_local! {
+    static EVENTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(event: String) {
+    EVENTS.with(|events| events.borrow_mut().push(event));
+}
+
+fn trace_named<F, R>(operation: &str, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    record(format!("trace {}", operation));
+    f()
+}
+
+#[decorate(
+    pre = {
+        println!("in {}", __fn_name);
+        record(format!("in {}", __fn_name));
+    },
+    post = record(format!("out {}", __fn_name)),
+    trace_named(__fn_name)
+)]
+fn compute_total(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+struct Service;
+
+impl Service {
+    #[decorate(trace_named(__fn_name))]
+    fn handle(&self) -> &'static str {
+        "handled"
+    }
+}
+
+fn main() {
+    assert_eq!(compute_total(1, 2), 3);
+    assert_eq!(Service.handle(), "handled");
+    assert_eq!(
+        EVENTS.with(|events| events.borrow().clone()),
+        ["trace compute_total", "in compute_total", "out compute_total", "trace handle"]
+    );
+}
@@ -0,0 +1,43 @@
+use decorate_macro::decorate;
+use std::future::Future;
+
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+// `transform_result` maps the awaited output rather than the future itself
+#[decorate(transform_result = double, log_execution)]
+fn delayed_sum(a: i32, b: i32) -> impl Future<Output = i32> {
+    async move {
+        tokio::task::yield_now().await;
+        a + b
+    }
+}
+
+#[decorate(transform_result = |s: String| s.to_uppercase(), log_execution)]
+fn greeting(name: String) -> impl Future<Output = String> + Send {
+    async move { format!("hello {name}") }
+}
+
+#[decorate(transform_result = double, log_execution)]
+async fn async_sum(a: i32, b: i32) -> i32 {
+    tokio::task::yield_now().await;
+    a + b
+}
+
+fn main() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        assert_eq!(delayed_sum(2, 3).await, 10);
+        assert_eq!(tokio::spawn(greeting("ada".to_string())).await.unwrap(), "HELLO ADA");
+        assert_eq!(async_sum(4, 5).await, 18);
+    });
+}
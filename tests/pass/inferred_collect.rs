@@ -0,0 +1,55 @@
+use decorate_macro::decorate;
+use std::collections::{BTreeMap, HashSet};
+use std::future::Future;
+
+// Bodies whose final expression is inferred from the declared return type,
+// like `collect()` or `parse()`, still infer through the decorator closure:
+// the closure's return type unifies with the function's.
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    f()
+}
+
+fn log_async<F, Fut, R>(f: F) -> impl Future<Output = R>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = R>,
+{
+    async move { f().await }
+}
+
+#[decorate(log_execution)]
+fn doubled(n: u32) -> Vec<u32> {
+    (0..n).map(|x| x * 2).collect()
+}
+
+#[decorate(log_execution, log_execution)]
+fn unique_lengths(words: &[&str]) -> HashSet<usize> {
+    words.iter().map(|w| w.len()).collect()
+}
+
+#[decorate(
+    retry_on_err = 2,
+    transform_result = |r: Result<u8, _>| r.map(|n| n + 1),
+    log_execution
+)]
+fn parse_digit(input: &str) -> Result<u8, std::num::ParseIntError> {
+    input.trim().parse()
+}
+
+#[decorate(log_async)]
+async fn indexed(words: Vec<&'static str>) -> BTreeMap<usize, &'static str> {
+    words.into_iter().enumerate().collect()
+}
+
+fn main() {
+    assert_eq!(doubled(4), vec![0, 2, 4, 6]);
+    assert_eq!(unique_lengths(&["a", "bb", "cc"]), HashSet::from([1, 2]));
+    assert_eq!(parse_digit(" 7 "), Ok(8));
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let map = runtime.block_on(indexed(vec!["x", "y"]));
+    assert_eq!(map, BTreeMap::from([(0, "x"), (1, "y")]));
+}
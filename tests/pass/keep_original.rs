@@ -0,0 +1,51 @@
+use decorate_macro::decorate;
+use std::fmt::Display;
+
+fn double_result(x: i32) -> i32 {
+    x * 2
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+#[decorate(keep_original = true, transform_result = double_result, log_execution)]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+/// Labels a value.
+#[decorate(keep_original = true, transform_result = |s: String| s.to_uppercase(), log_execution)]
+pub fn label<T>(value: T, prefix: &str) -> String
+where
+    T: Display,
+{
+    format!("{prefix}: {value}")
+}
+
+struct Counter {
+    step: u32,
+}
+
+impl Counter {
+    #[decorate(keep_original = true, transform_result = |n| n + 100, log_execution)]
+    fn advance(&self, from: u32) -> u32 {
+        from + self.step
+    }
+}
+
+fn main() {
+    assert_eq!(add(2, 3), 10);
+    assert_eq!(add_undecorated(2, 3), 5);
+
+    assert_eq!(label(7, "id"), "ID: 7");
+    assert_eq!(label_undecorated(7, "id"), "id: 7");
+
+    let counter = Counter { step: 2 };
+    assert_eq!(counter.advance(1), 103);
+    assert_eq!(counter.advance_undecorated(1), 3);
+}
@@ -0,0 +1,65 @@
+use decorate_macro::decorate;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static POST_RUNS: AtomicU32 = AtomicU32::new(0);
+
+fn log_call<F: FnOnce() -> R, R>(f: F) -> R {
+    f()
+}
+
+// `break 'body` leaves only the body: `post` and `transform_result` still run
+#[decorate(
+    labeled_body = true,
+    post = POST_RUNS.fetch_add(1, Ordering::SeqCst),
+    transform_result = |n: i32| n * 10,
+    log_call
+)]
+fn first_negative(values: &[i32]) -> i32 {
+    for &value in values {
+        if value < 0 {
+            break 'body value;
+        }
+    }
+    0
+}
+
+// Bodies that never break don't warn about the unused label
+#[deny(unused_labels)]
+#[decorate(labeled_body = true, log_call)]
+fn plain(x: u32) -> u32 {
+    x + 1
+}
+
+// The undecorated sibling keeps the label too
+#[decorate(labeled_body = true, keep_original = true, log_call)]
+fn clamp_to_ten(x: u32) -> u32 {
+    if x > 10 {
+        break 'body 10;
+    }
+    x
+}
+
+#[decorate(labeled_body = true, log_call)]
+async fn first_even(values: Vec<u32>) -> Option<u32> {
+    for value in values {
+        if value % 2 == 0 {
+            break 'body Some(value);
+        }
+        tokio::task::yield_now().await;
+    }
+    None
+}
+
+fn main() {
+    assert_eq!(first_negative(&[3, -2, -5]), -20);
+    assert_eq!(POST_RUNS.load(Ordering::SeqCst), 1);
+    assert_eq!(first_negative(&[1, 2]), 0);
+    assert_eq!(POST_RUNS.load(Ordering::SeqCst), 2);
+
+    assert_eq!(plain(1), 2);
+    assert_eq!(clamp_to_ten(42), 10);
+    assert_eq!(clamp_to_ten_undecorated(42), 10);
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    assert_eq!(runtime.block_on(first_even(vec![1, 3, 4, 6])), Some(4));
+}
@@ -0,0 +1,55 @@
+use decorate_macro::decorate;
+
+// Expands to statements followed by a tail expression. Rust only accepts this
+// for brace-delimited invocations, which are parsed as statements.
+macro_rules! compute {
+    ($x:expr) => {
+        let doubled = $x * 2;
+        let offset = 1;
+        doubled + offset
+    };
+}
+
+// Expands to an item followed by statements.
+macro_rules! with_helper {
+    ($x:expr) => {
+        fn helper(v: i32) -> i32 {
+            v * 10
+        }
+        let scaled = helper($x);
+        scaled
+    };
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+fn negate(x: i32) -> i32 {
+    -x
+}
+
+#[decorate(log_execution)]
+fn compute_value(x: i32) -> i32 {
+    compute! { x }
+}
+
+#[decorate(pre = println!("before"), post = println!("after"), log_execution)]
+fn compute_with_hooks(x: i32) -> i32 {
+    compute! { x }
+}
+
+#[decorate(transform_result = negate, log_execution)]
+fn compute_with_item(x: i32) -> i32 {
+    with_helper! { x }
+}
+
+fn main() {
+    assert_eq!(compute_value(4), 9);
+    assert_eq!(compute_with_hooks(4), 9);
+    assert_eq!(compute_with_item(3), -30);
+}
@@ -0,0 +1,53 @@
+use decorate_macro::decorate;
+use std::cell::RefCell;
+use std::time::Duration;
+
+thread_local! {
+    static SLOW_CALLS: RefCell<Vec<Duration>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record_slow(elapsed: Duration) {
+    SLOW_CALLS.with(|calls| calls.borrow_mut().push(elapsed));
+}
+
+fn slow_calls() -> usize {
+    SLOW_CALLS.with(|calls| calls.borrow().len())
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    f()
+}
+
+// Exceeding the limit calls the handler, but the result is still returned
+#[decorate(max_duration = Duration::from_millis(20), on_slow = record_slow, log_execution)]
+fn lookup(delay_ms: u64) -> u64 {
+    std::thread::sleep(Duration::from_millis(delay_ms));
+    delay_ms
+}
+
+#[decorate(
+    max_duration = Duration::from_millis(20),
+    on_slow = |elapsed| record_slow(elapsed),
+    transform_result = |n: u64| n + 1
+)]
+async fn fetch(delay_ms: u64) -> u64 {
+    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+    delay_ms
+}
+
+fn main() {
+    assert_eq!(lookup(0), 0);
+    assert_eq!(slow_calls(), 0);
+    assert_eq!(lookup(40), 40);
+    assert_eq!(slow_calls(), 1);
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    assert_eq!(runtime.block_on(fetch(0)), 1);
+    assert_eq!(slow_calls(), 1);
+    assert_eq!(runtime.block_on(fetch(40)), 41);
+    assert_eq!(slow_calls(), 2);
+    assert!(SLOW_CALLS.with(|calls| calls.borrow()[1] >= Duration::from_millis(40)));
+}
@@ -0,0 +1,38 @@
+use decorate_macro::decorate;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+
+fn with_retry<F, R>(attempts: u32, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    ATTEMPTS.store(attempts, Ordering::SeqCst);
+    f()
+}
+
+fn with_threshold<F, R>(min: i32, max: i32, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    assert!(min <= max, "Invalid threshold range");
+    f()
+}
+
+#[decorate(with_retry(attempts = 3))]
+fn named() -> i32 {
+    42
+}
+
+#[decorate(with_threshold(min = 0, 100), with_retry(attempts = 5))]
+fn mixed(x: i32) -> i32 {
+    x * 2
+}
+
+fn main() {
+    assert_eq!(named(), 42);
+    assert_eq!(ATTEMPTS.load(Ordering::SeqCst), 3);
+
+    assert_eq!(mixed(21), 42);
+    assert_eq!(ATTEMPTS.load(Ordering::SeqCst), 5);
+}
@@ -0,0 +1,54 @@
+use decorate_macro::decorate;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+async fn async_log_execution<F, Fut, R>(f: F) -> R
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = R>,
+{
+    println!("Executing async function");
+    f().await
+}
+
+#[decorate(
+    on_none = WARNINGS.lock().unwrap().push(format!("{}: user {} not found", __fn_name, id)),
+    on_some = println!("found user {}", id),
+    log_execution
+)]
+fn find_user(users: &HashMap<u32, String>, id: u32) -> Option<String> {
+    users.get(&id).cloned()
+}
+
+#[decorate(on_none = WARNINGS.lock().unwrap().push(format!("{}: empty", __fn_name)), async_log_execution)]
+async fn first_word(text: &str) -> Option<String> {
+    text.split_whitespace().next().map(str::to_string)
+}
+
+fn main() {
+    let users = HashMap::from([(1, "alice".to_string())]);
+
+    assert_eq!(find_user(&users, 1), Some("alice".to_string()));
+    assert!(WARNINGS.lock().unwrap().is_empty());
+
+    assert_eq!(find_user(&users, 2), None);
+    assert_eq!(*WARNINGS.lock().unwrap(), ["find_user: user 2 not found"]);
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    assert_eq!(runtime.block_on(first_word("hello world")), Some("hello".to_string()));
+    assert_eq!(runtime.block_on(first_word("   ")), None);
+    assert_eq!(
+        *WARNINGS.lock().unwrap(),
+        ["find_user: user 2 not found", "first_word: empty"]
+    );
+}
@@ -0,0 +1,46 @@
+use decorate_macro::decorate;
+use std::cell::RefCell;
+
+thread_local! {
+    static EVENTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(event: String) {
+    EVENTS.with(|events| events.borrow_mut().push(event));
+}
+
+fn log_inputs<F, R>(args: (i32, i32), f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    record(format!("inputs {:?}", args));
+    f()
+}
+
+fn log_input_named<A, F, R>(label: &str, args: A, f: F) -> R
+where
+    A: std::fmt::Debug,
+    F: FnOnce() -> R,
+{
+    record(format!("{} {:?}", label, args));
+    f()
+}
+
+#[decorate(pass_args = true, log_inputs)]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[decorate(pass_args = true, log_input_named("square"))]
+fn square(x: u64) -> u64 {
+    x * x
+}
+
+fn main() {
+    assert_eq!(add(2, 3), 5);
+    assert_eq!(square(4), 16);
+    assert_eq!(
+        EVENTS.with(|events| events.borrow().clone()),
+        ["inputs (2, 3)", "square 4"]
+    );
+}
@@ -0,0 +1,44 @@
+use decorate_macro::decorate;
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn swap(a: i32, b: i32) -> (i32, i32) {
+    (b, a)
+}
+
+fn clamp_all(a: i32, b: i32, c: i32) -> (i32, i32, i32) {
+    (a.max(0), b.max(0), c.max(0))
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+#[decorate(
+    pre = println!("diff of {} and {}", a, b),
+    transform_params = swap,
+    log_execution
+)]
+fn diff([a, b]: [i32; 2]) -> i32 {
+    a - b
+}
+
+#[decorate(transform_params = clamp_all, log_execution)]
+fn sum((a, b): (i32, i32), Point { x: c, .. }: Point) -> i32 {
+    a + b + c
+}
+
+fn main() {
+    // Swapped to [3, 10]
+    assert_eq!(diff([10, 3]), -7);
+    // Clamped to 0 + 2 + 0
+    assert_eq!(sum((-1, 2), Point { x: -3, y: 9 }), 2);
+    assert_eq!(Point { x: 0, y: 9 }.y, 9);
+}
@@ -0,0 +1,70 @@
+use decorate_macro::decorate;
+use std::cell::Cell;
+use std::num::ParseIntError;
+
+thread_local! {
+    static ATTEMPTS: Cell<u32> = const { Cell::new(0) };
+}
+
+fn attempt() -> u32 {
+    ATTEMPTS.with(|attempts| {
+        attempts.set(attempts.get() + 1);
+        attempts.get()
+    })
+}
+
+fn reset() {
+    ATTEMPTS.with(|attempts| attempts.set(0));
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+#[decorate(retry_on_err = 3, log_execution)]
+fn flaky(fail_times: u32) -> Result<u32, String> {
+    let n = attempt();
+    if n <= fail_times {
+        Err(format!("attempt {n} failed"))
+    } else {
+        Ok(n)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct ParseError;
+
+impl From<ParseIntError> for ParseError {
+    fn from(_: ParseIntError) -> Self {
+        ParseError
+    }
+}
+
+// `?` fails the current attempt rather than returning from the function
+#[decorate(retry_on_err = 2)]
+fn parse_eventually(inputs: &[&str]) -> Result<i32, ParseError> {
+    let index = attempt() as usize - 1;
+    let value: i32 = inputs[index].parse()?;
+    Ok(value)
+}
+
+fn main() {
+    // Errors twice, then succeeds on the third try
+    reset();
+    assert_eq!(flaky(2), Ok(3));
+    assert_eq!(ATTEMPTS.with(Cell::get), 3);
+
+    // Gives up after the initial attempt plus three retries
+    reset();
+    assert_eq!(flaky(10), Err("attempt 4 failed".to_string()));
+    assert_eq!(ATTEMPTS.with(Cell::get), 4);
+
+    reset();
+    assert_eq!(parse_eventually(&["x", "y", "42"]), Ok(42));
+    reset();
+    assert_eq!(parse_eventually(&["x", "y", "z", "42"]), Err(ParseError));
+}
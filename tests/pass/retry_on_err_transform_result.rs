@@ -0,0 +1,52 @@
+use decorate_macro::decorate;
+use std::cell::Cell;
+use std::fmt::Display;
+use std::num::ParseIntError;
+
+thread_local! {
+    static ATTEMPTS: Cell<u32> = const { Cell::new(0) };
+}
+
+fn attempt() -> usize {
+    ATTEMPTS.with(|attempts| {
+        attempts.set(attempts.get() + 1);
+        attempts.get() as usize
+    })
+}
+
+fn reset() {
+    ATTEMPTS.with(|attempts| attempts.set(0));
+}
+
+fn describe_error(result: Result<i32, ParseIntError>) -> Result<i32, String> {
+    result.map_err(|e| format!("gave up: {e}"))
+}
+
+// The retried body yields `Result<i32, ParseIntError>`; the transform then
+// changes the error type to the declared one
+#[decorate(transform_result = describe_error, retry_on_err = 2)]
+fn parse_eventually(inputs: &[&str]) -> Result<i32, String> {
+    let value: i32 = inputs[attempt() - 1].parse()?;
+    Ok(value)
+}
+
+#[decorate(retry_on_err = 1)]
+fn displayable(inputs: &[&str]) -> Result<impl Display, ParseIntError> {
+    let value: i32 = inputs[attempt() - 1].parse()?;
+    Ok(value)
+}
+
+fn main() {
+    reset();
+    assert_eq!(parse_eventually(&["x", "7"]), Ok(7));
+    reset();
+    assert_eq!(
+        parse_eventually(&["x", "y", "z"]),
+        Err("gave up: invalid digit found in string".to_string())
+    );
+
+    reset();
+    assert_eq!(displayable(&["x", "5"]).unwrap().to_string(), "5");
+    reset();
+    assert!(displayable(&["x", "y"]).is_err());
+}
@@ -0,0 +1,57 @@
+use decorate_macro::decorate;
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    f()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Vec2 {
+    x: i32,
+    y: i32,
+}
+
+impl Vec2 {
+    fn normalize(a: Self, b: Self) -> (Self, Self) {
+        (a.abs(), b.abs())
+    }
+
+    fn abs(self) -> Self {
+        Self {
+            x: self.x.abs(),
+            y: self.y.abs(),
+        }
+    }
+
+    // `Self` in the transform's path and in the parameter types is resolved
+    // inside the `impl`, where the generated code lives
+    #[decorate(transform_params = Self::normalize, log_execution)]
+    fn combine(a: Self, b: Self) -> Self {
+        Self {
+            x: a.x + b.x,
+            y: a.y + b.y,
+        }
+    }
+
+    #[decorate(
+        transform_params = |a: Self, b: Self| (b, a),
+        transform_result = |v: Self| Self { x: v.x * 10, ..v },
+        log_execution
+    )]
+    fn difference(a: Self, b: Self) -> Self {
+        Self {
+            x: a.x - b.x,
+            y: a.y - b.y,
+        }
+    }
+}
+
+fn main() {
+    let a = Vec2 { x: -1, y: 2 };
+    let b = Vec2 { x: 3, y: -4 };
+
+    assert_eq!(Vec2::combine(a, b), Vec2 { x: 4, y: 6 });
+    assert_eq!(Vec2::difference(a, b), Vec2 { x: 40, y: -6 });
+}
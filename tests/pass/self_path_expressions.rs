@@ -0,0 +1,95 @@
+use decorate_macro::decorate;
+use std::cell::RefCell;
+use std::fmt::Debug;
+
+struct Logger {
+    prefix: &'static str,
+    lines: RefCell<Vec<String>>,
+}
+
+impl Logger {
+    fn new(prefix: &'static str) -> Self {
+        Self {
+            prefix,
+            lines: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn log<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+        R: Debug,
+    {
+        let result = f();
+        self.lines
+            .borrow_mut()
+            .push(format!("{}: {:?}", self.prefix, result));
+        result
+    }
+
+    /// Returns a decorator bound to this logger.
+    fn logger<F, R>(&self) -> impl FnOnce(F) -> R + '_
+    where
+        F: FnOnce() -> R,
+        R: Debug,
+    {
+        move |f| self.log(f)
+    }
+}
+
+struct Inner {
+    logger: Logger,
+}
+
+impl Inner {
+    fn logger<F, R>(&self) -> impl FnOnce(F) -> R + '_
+    where
+        F: FnOnce() -> R,
+        R: Debug,
+    {
+        self.logger.logger()
+    }
+}
+
+struct Service {
+    inner: Inner,
+    pair: (Logger, Logger),
+    pool: Vec<Logger>,
+}
+
+impl Service {
+    #[decorate("self.inner.logger()")]
+    fn via_method(&self, x: i32) -> i32 {
+        x + 1
+    }
+
+    #[decorate("self.pair.1.log")]
+    fn via_tuple_field(&self, x: i32) -> i32 {
+        x * 2
+    }
+
+    #[decorate("self.pool[0].log", "self.pool.get(1).unwrap().log")]
+    fn via_index(&self, x: i32) -> i32 {
+        x - 1
+    }
+}
+
+fn main() {
+    let service = Service {
+        inner: Inner {
+            logger: Logger::new("inner"),
+        },
+        pair: (Logger::new("first"), Logger::new("second")),
+        pool: vec![Logger::new("pool0"), Logger::new("pool1")],
+    };
+
+    assert_eq!(service.via_method(1), 2);
+    assert_eq!(service.via_tuple_field(3), 6);
+    assert_eq!(service.via_index(10), 9);
+
+    assert_eq!(*service.inner.logger.lines.borrow(), ["inner: 2"]);
+    assert!(service.pair.0.lines.borrow().is_empty());
+    assert_eq!(*service.pair.1.lines.borrow(), ["second: 6"]);
+    assert_eq!(*service.pool[0].lines.borrow(), ["pool0: 9"]);
+    assert_eq!(*service.pool[1].lines.borrow(), ["pool1: 9"]);
+}
@@ -0,0 +1,55 @@
+use decorate_macro::decorate;
+use std::cell::{Cell, RefCell};
+
+// Per-thread decorator configuration. The decorator expression is evaluated in
+// the function body on every call, so it sees the thread's current setting.
+#[derive(Clone)]
+struct Tagger {
+    tag: &'static str,
+}
+
+impl Tagger {
+    fn decorator<F>(self) -> impl FnOnce(F) -> String
+    where
+        F: FnOnce() -> String,
+    {
+        move |f| format!("[{}] {}", self.tag, f())
+    }
+}
+
+thread_local! {
+    static CURRENT_DECORATOR: RefCell<Tagger> = const { RefCell::new(Tagger { tag: "default" }) };
+    static SCALE: Cell<i32> = const { Cell::new(1) };
+}
+
+fn make_scaler<F>(factor: i32) -> impl FnOnce(F) -> i32
+where
+    F: FnOnce() -> i32,
+{
+    move |f| f() * factor
+}
+
+#[decorate(CURRENT_DECORATOR.with(|d| d.borrow().clone().decorator()))]
+fn greet(name: &str) -> String {
+    format!("hello {name}")
+}
+
+#[decorate(SCALE.with(|scale| make_scaler(scale.get())), transform_result = |n: i32| n + 1)]
+fn seven() -> i32 {
+    7
+}
+
+fn main() {
+    assert_eq!(greet("ann"), "[default] hello ann");
+    CURRENT_DECORATOR.with(|d| *d.borrow_mut() = Tagger { tag: "audit" });
+    assert_eq!(greet("bob"), "[audit] hello bob");
+
+    assert_eq!(seven(), 8);
+    SCALE.with(|scale| scale.set(3));
+    assert_eq!(seven(), 24);
+
+    // Other threads keep their own configuration
+    std::thread::spawn(|| assert_eq!(seven(), 8))
+        .join()
+        .unwrap();
+}
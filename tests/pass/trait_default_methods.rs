@@ -0,0 +1,71 @@
+use decorate_macro::decorate;
+
+fn double_result(x: i32) -> i32 {
+    x * 2
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+fn add_one<F>(f: F) -> i32
+where
+    F: FnOnce() -> i32,
+{
+    f() + 1
+}
+
+trait Shape {
+    fn side(&self) -> i32;
+
+    #[decorate(transform_result = double_result, log_execution)]
+    fn perimeter_half(&self) -> i32 {
+        self.side() * 2
+    }
+
+    #[decorate(keep_original = true, add_one)]
+    fn area(&self) -> i32 {
+        self.side() * self.side()
+    }
+
+    #[decorate(transform_result = |_: &'static str| __fn_name, log_execution)]
+    fn name() -> &'static str {
+        ""
+    }
+}
+
+struct Square(i32);
+
+impl Shape for Square {
+    fn side(&self) -> i32 {
+        self.0
+    }
+}
+
+struct Fixed;
+
+impl Shape for Fixed {
+    fn side(&self) -> i32 {
+        1
+    }
+
+    // Overrides replace the decorated default entirely
+    fn area(&self) -> i32 {
+        0
+    }
+}
+
+fn main() {
+    let square = Square(3);
+    assert_eq!(square.perimeter_half(), 12);
+    assert_eq!(square.area(), 10);
+    assert_eq!(square.area_undecorated(), 9);
+    assert_eq!(Square::name(), "name");
+
+    assert_eq!(Fixed.perimeter_half(), 4);
+    assert_eq!(Fixed.area(), 0);
+}
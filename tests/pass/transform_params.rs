@@ -39,11 +39,24 @@ fn compute(x: i32, y: i32) -> i32 {
     x + y
 }
 
+#[decorate(transform_result = |r| r.trim().to_string(), log_execution)]
+fn greet(name: &str) -> String {
+    format!("  hello {name}  ")
+}
+
+#[decorate(transform_params = |x, y| (i32::max(x, 0), i32::max(y, 0)), log_execution)]
+fn clamp_sum(x: i32, y: i32) -> i32 {
+    x + y
+}
+
 fn main() {
     let result = compute(1, 2);
     // (1+1) + (2+1) = 5
     // 5 * 2 = 10
     assert_eq!(result, 10);
+
+    assert_eq!(greet("world"), "hello world");
+    assert_eq!(clamp_sum(-3, 4), 4);
 }
 
 #[test]
@@ -133,3 +146,17 @@ fn test_transform_order() {
 
     assert_eq!(add(1, 2), 10); // ((1+1) + (2+1)) * 2 = 10
 }
+
+#[test]
+fn test_closure_transforms() {
+    #[decorate(
+        transform_params = |x: i32, y: i32| (x.abs(), y.abs()),
+        transform_result = |r| r * 10,
+        log_execution
+    )]
+    fn add(x: i32, y: i32) -> i32 {
+        x + y
+    }
+
+    assert_eq!(add(-1, 2), 30); // (1 + 2) * 10 = 30
+}
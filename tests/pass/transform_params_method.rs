@@ -0,0 +1,33 @@
+use decorate_macro::decorate;
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+struct Canvas {
+    max: i32,
+}
+
+impl Canvas {
+    fn clamp_params(&self, x: i32, y: i32) -> (i32, i32) {
+        (x.clamp(0, self.max), y.clamp(0, self.max))
+    }
+
+    #[decorate(transform_params_method = "self.clamp_params", log_execution)]
+    fn area(&self, x: i32, y: i32) -> i32 {
+        x * y
+    }
+}
+
+fn main() {
+    let canvas = Canvas { max: 10 };
+    assert_eq!(canvas.area(3, 4), 12);
+    // Clamped to (10, 0)
+    assert_eq!(canvas.area(25, -4), 0);
+    // Clamped to (10, 10)
+    assert_eq!(canvas.area(11, 12), 100);
+}
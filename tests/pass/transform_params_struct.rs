@@ -0,0 +1,32 @@
+use decorate_macro::decorate;
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn clamp_to_origin(x: i32, y: i32) -> Point {
+    Point {
+        x: x.max(0),
+        y: y.max(0),
+    }
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+#[decorate(transform_params_struct = (clamp_to_origin, Point), log_execution)]
+fn manhattan(x: i32, y: i32) -> i32 {
+    x + y
+}
+
+fn main() {
+    assert_eq!(manhattan(3, 4), 7);
+    // (-3).max(0) + 4 = 4
+    assert_eq!(manhattan(-3, 4), 4);
+}
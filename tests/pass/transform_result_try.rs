@@ -0,0 +1,39 @@
+use decorate_macro::decorate;
+use std::num::ParseIntError;
+
+#[derive(Debug, PartialEq)]
+enum AppError {
+    InvalidNumber(String),
+}
+
+impl From<ParseIntError> for AppError {
+    fn from(e: ParseIntError) -> Self {
+        AppError::InvalidNumber(e.to_string())
+    }
+}
+
+fn into_app_error(result: Result<i32, ParseIntError>) -> Result<i32, AppError> {
+    result.map_err(AppError::from)
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+#[decorate(transform_result_try = into_app_error, log_execution)]
+fn parse_and_double(input: &str) -> Result<i32, AppError> {
+    let value: i32 = input.trim().parse()?;
+    Ok(value * 2)
+}
+
+fn main() {
+    assert_eq!(parse_and_double(" 21 "), Ok(42));
+    assert!(matches!(
+        parse_and_double("abc"),
+        Err(AppError::InvalidNumber(_))
+    ));
+}
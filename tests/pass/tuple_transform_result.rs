@@ -0,0 +1,55 @@
+use decorate_macro::decorate;
+use std::future::Future;
+
+// A `transform_result` closure can destructure a tuple result in its parameter
+// pattern and rebuild it, changing only the elements it needs to.
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    f()
+}
+
+fn log_async<F, Fut, R>(f: F) -> impl Future<Output = R>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = R>,
+{
+    async move { f().await }
+}
+
+fn swap<A, B>((a, b): (A, B)) -> (B, A) {
+    (b, a)
+}
+
+#[decorate(transform_result = |(count, label)| (count + 1, label), log_execution)]
+fn tally(words: &[&str]) -> (usize, String) {
+    (words.len(), words.join(","))
+}
+
+#[decorate(transform_result = |(min, _, max)| (min, max - min, max))]
+fn bounds(values: &[i32]) -> (i32, i32, i32) {
+    let min = *values.iter().min().unwrap();
+    let max = *values.iter().max().unwrap();
+    (min, 0, max)
+}
+
+#[decorate(transform_result = swap, log_execution)]
+fn split(input: &str) -> (u32, u32) {
+    let (left, right) = input.split_once(':').unwrap();
+    (left.parse().unwrap(), right.parse().unwrap())
+}
+
+#[decorate(transform_result = |(id, ok)| (id * 10, !ok), log_async)]
+async fn probe(id: u32) -> (u32, bool) {
+    (id, id % 2 == 0)
+}
+
+fn main() {
+    assert_eq!(tally(&["a", "b"]), (3, "a,b".to_string()));
+    assert_eq!(bounds(&[4, -2, 9]), (-2, 11, 9));
+    assert_eq!(split("3:7"), (7, 3));
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    assert_eq!(runtime.block_on(probe(4)), (40, false));
+}
@@ -0,0 +1,39 @@
+use decorate_macro::decorate;
+use std::future::Future;
+
+// Decorated async bodies are awaited in place rather than boxed, so state held
+// across an `.await` lives inside the function's own future.
+fn log_call<F, Fut, R>(f: F) -> impl Future<Output = R>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = R>,
+{
+    async move {
+        let result = f().await;
+        println!("Async function completed");
+        result
+    }
+}
+
+#[decorate(log_call)]
+async fn checksum(seed: u8) -> u32 {
+    let buffer = [seed; 4096];
+    tokio::task::yield_now().await;
+    buffer.iter().map(|&b| b as u32).sum()
+}
+
+async fn undecorated_checksum(seed: u8) -> u32 {
+    let buffer = [seed; 4096];
+    tokio::task::yield_now().await;
+    buffer.iter().map(|&b| b as u32).sum()
+}
+
+fn main() {
+    // A boxed body would leave only a pointer-sized future here
+    assert!(std::mem::size_of_val(&checksum(1)) >= 4096);
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        assert_eq!(checksum(2).await, undecorated_checksum(2).await);
+    });
+}
@@ -0,0 +1,53 @@
+use decorate_macro::decorate;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static TRACING_ENABLED: AtomicBool = AtomicBool::new(false);
+
+fn explode<F, R>(_f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    panic!("decorator should have been bypassed")
+}
+
+fn double_result(x: i32) -> i32 {
+    x * 2
+}
+
+fn log_execution<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    println!("Executing function");
+    f()
+}
+
+#[decorate(when = false, explode)]
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[decorate(
+    transform_result = double_result,
+    log_execution,
+    when = TRACING_ENABLED.load(Ordering::Relaxed)
+)]
+fn scaled(x: i32) -> i32 {
+    x + 1
+}
+
+#[decorate(when = !name.is_empty(), transform_result = |s: String| s.to_uppercase(), log_execution)]
+fn greet(name: &str) -> String {
+    format!("hello {name}")
+}
+
+fn main() {
+    assert_eq!(add(2, 3), 5);
+
+    assert_eq!(scaled(1), 2);
+    TRACING_ENABLED.store(true, Ordering::Relaxed);
+    assert_eq!(scaled(1), 4);
+
+    assert_eq!(greet("ann"), "HELLO ANN");
+    assert_eq!(greet(""), "hello ");
+}